@@ -10,6 +10,37 @@ pub enum Error {
     ProposalNotReadyForExecution,
     InvalidProposal,
     AlreadyRegistered,
+    AlreadyEndorsed,
+    NotEndorsed,
+    AlreadyWatching,
+    NotWatching,
+    WatchlistFull,
+    VotingPeriodTooShort,
+    VotingPeriodTooLong,
+    ExecutionDelayTooShort,
+    ArithmeticOverflow,
+    VoterInProbation,
+    ContractPaused,
+    TooManyActiveProposals,
+    ContractAccountsNotAllowed,
+    ExecutionFailed,
+    InvalidProof,
+    GovernanceHalted,
+    InsufficientVotingPower,
+    AlreadyFlagged,
+    FlaggingBarred,
+    VoterCapReached,
+    OracleConditionNotMet,
+    InvalidAmount,
+    InsufficientTreasuryFunds,
+    NotRegistered,
+    InvalidDelegation,
+    NotVoted,
+    SameVote,
+    ProposalExpired,
+    VotesAlreadyCast,
+    OptionMismatch,
+    StakeLocked,
 }
 
 pub type Result<T> = core::result::Result<T, Error>;
\ No newline at end of file
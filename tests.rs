@@ -13,9 +13,18 @@ fn create_test_proposal_params() -> (String, String, ProposalType, GovernancePar
         voting_period: VotingPeriod::SevenDays,
         quorum_threshold: QuorumThreshold::Ten,
         execution_delay: ExecutionDelay::OneDay,
+        execution_delay_override: None,
+        scheduled_activation: None,
+        eligible_voters: None,
+        oracle: None,
+        grace_period: None,
+        allow_cancel_after_votes: false,
     };
     let voting_options = VotingOptions {
         options: vec!["Yes".to_string(), "No".to_string()],
+        payloads: vec![],
+        actions: vec![],
+        abstain_index: None,
     };
     (title, description, proposal_type, governance_params, voting_options)
 }
@@ -31,7 +40,7 @@ mod tests {
         let mut contract = TreasuryGovernance::new();
         let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
         
-        let result = contract.create_proposal(title.clone(), description, proposal_type, governance_params, voting_options);
+        let result = contract.create_proposal(title.clone(), description, proposal_type, governance_params, voting_options, vec![]);
         assert!(result.is_ok());
         
         let proposal_id = result.unwrap();
@@ -54,9 +63,12 @@ mod tests {
         let (title, description, proposal_type, governance_params, _) = create_test_proposal_params();
         let voting_options = VotingOptions {
             options: vec![],
+            payloads: vec![],
+            actions: vec![],
+            abstain_index: None,
         };
         
-        let result = contract.create_proposal(title, description, proposal_type, governance_params, voting_options);
+        let result = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]);
         assert!(result.is_err());
         assert_eq!(result.unwrap_err(), crate::errors::Error::InvalidProposal);
     }
@@ -70,9 +82,12 @@ mod tests {
         let (title, description, proposal_type, governance_params, _) = create_test_proposal_params();
         let voting_options = VotingOptions {
             options: (1..=11).map(|i| format!("Option {}", i)).collect(),
+            payloads: vec![],
+            actions: vec![],
+            abstain_index: None,
         };
         
-        let result = contract.create_proposal(title, description, proposal_type, governance_params, voting_options);
+        let result = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]);
         assert!(result.is_err());
         assert_eq!(result.unwrap_err(), crate::errors::Error::InvalidProposal);
     }
@@ -86,9 +101,12 @@ mod tests {
         let (title, description, proposal_type, governance_params, _) = create_test_proposal_params();
         let voting_options = VotingOptions {
             options: vec!["Valid Option".to_string(), "".to_string()],
+            payloads: vec![],
+            actions: vec![],
+            abstain_index: None,
         };
         
-        let result = contract.create_proposal(title, description, proposal_type, governance_params, voting_options);
+        let result = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]);
         assert!(result.is_err());
         assert_eq!(result.unwrap_err(), crate::errors::Error::InvalidProposal);
     }
@@ -103,11 +121,11 @@ mod tests {
         
         // Test different voting periods
         governance_params.voting_period = VotingPeriod::ThreeDays;
-        let result = contract.create_proposal(title.clone(), description.clone(), proposal_type.clone(), governance_params.clone(), voting_options.clone());
+        let result = contract.create_proposal(title.clone(), description.clone(), proposal_type.clone(), governance_params.clone(), voting_options.clone(), vec![]);
         assert!(result.is_ok());
         
         let proposal = contract.get_proposal(1).unwrap();
-        let expected_end = proposal.created_at + (3 * 24 * 60 * 60);
+        let expected_end = proposal.created_at + 3 * MILLIS_PER_DAY;
         assert_eq!(proposal.voting_end, expected_end);
     }
 
@@ -120,11 +138,11 @@ mod tests {
         let (title, description, proposal_type, mut governance_params, voting_options) = create_test_proposal_params();
         
         governance_params.execution_delay = ExecutionDelay::TwoDays;
-        let result = contract.create_proposal(title, description, proposal_type, governance_params, voting_options);
+        let result = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]);
         assert!(result.is_ok());
         
         let proposal = contract.get_proposal(1).unwrap();
-        let expected_execution = proposal.voting_end + (2 * 24 * 60 * 60);
+        let expected_execution = proposal.voting_end + 2 * MILLIS_PER_DAY;
         assert_eq!(proposal.execution_time, expected_execution);
     }
 
@@ -141,7 +159,7 @@ mod tests {
         
         // Create proposal
         let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
-        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options).unwrap();
+        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
         
         // Vote
         let vote_choice = VoteChoice {
@@ -176,7 +194,7 @@ mod tests {
         
         // Create proposal
         let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
-        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options).unwrap();
+        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
         
         // First vote
         let vote_choice = VoteChoice {
@@ -203,7 +221,7 @@ mod tests {
         
         // Create proposal
         let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
-        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options).unwrap();
+        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
         
         // Vote with invalid option index
         let vote_choice = VoteChoice {
@@ -228,11 +246,11 @@ mod tests {
         
         // Create proposal
         let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
-        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options).unwrap();
+        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
         
         // Advance time past voting period
         let proposal = contract.get_proposal(proposal_id).unwrap();
-        set_block_timestamp::<ink::env::DefaultEnvironment>((proposal.voting_end + 1) as u64);
+        set_block_timestamp::<ink::env::DefaultEnvironment>(proposal.voting_end + 1);
         
         // Vote should fail
         let vote_choice = VoteChoice {
@@ -254,7 +272,7 @@ mod tests {
         
         // Create proposal without registering
         let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
-        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options).unwrap();
+        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
         
         // Vote should fail
         let vote_choice = VoteChoice {
@@ -264,7 +282,7 @@ mod tests {
         
         let result = contract.vote(proposal_id, vote_choice);
         assert!(result.is_err());
-        assert_eq!(result.unwrap_err(), crate::errors::Error::NotAuthorized);
+        assert_eq!(result.unwrap_err(), crate::errors::Error::NotRegistered);
     }
 
 
@@ -285,7 +303,7 @@ mod tests {
         // Create proposal
         set_caller(accounts.alice);
         let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
-        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options).unwrap();
+        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
         
         // Vote to meet quorum (10% of 3 voters = 1 vote needed)
         let vote_choice = VoteChoice {
@@ -296,7 +314,7 @@ mod tests {
         
         // Advance time past voting period
         let proposal = contract.get_proposal(proposal_id).unwrap();
-        set_block_timestamp::<ink::env::DefaultEnvironment>((proposal.voting_end + 1) as u64);
+        set_block_timestamp::<ink::env::DefaultEnvironment>(proposal.voting_end + 1);
         
         // Update status
         let result = contract.update_proposal_status(proposal_id);
@@ -321,13 +339,13 @@ mod tests {
         // Create proposal with higher quorum requirement
         let (title, description, proposal_type, mut governance_params, voting_options) = create_test_proposal_params();
         governance_params.quorum_threshold = QuorumThreshold::Twenty; // 20% quorum
-        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options).unwrap();
+        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
         
         // Don't vote (no votes cast)
         
         // Advance time past voting period
         let proposal = contract.get_proposal(proposal_id).unwrap();
-        set_block_timestamp::<ink::env::DefaultEnvironment>((proposal.voting_end + 1) as u64);
+        set_block_timestamp::<ink::env::DefaultEnvironment>(proposal.voting_end + 1);
         
         // Update status
         let result = contract.update_proposal_status(proposal_id);
@@ -354,7 +372,7 @@ mod tests {
         // Create proposal
         set_caller(accounts.alice);
         let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
-        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options).unwrap();
+        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
         
         // Create a tie (1 vote each)
         let vote_choice_1 = VoteChoice {
@@ -372,7 +390,7 @@ mod tests {
         
         // Advance time past voting period
         let proposal = contract.get_proposal(proposal_id).unwrap();
-        set_block_timestamp::<ink::env::DefaultEnvironment>((proposal.voting_end + 1) as u64);
+        set_block_timestamp::<ink::env::DefaultEnvironment>(proposal.voting_end + 1);
         
         // Update status
         let result = contract.update_proposal_status(proposal_id);
@@ -396,7 +414,7 @@ mod tests {
         
         // Create proposal
         let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
-        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options).unwrap();
+        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
         
         // Vote and update status to passed
         let vote_choice = VoteChoice {
@@ -406,7 +424,7 @@ mod tests {
         contract.vote(proposal_id, vote_choice).unwrap();
         
         let proposal = contract.get_proposal(proposal_id).unwrap();
-        set_block_timestamp::<ink::env::DefaultEnvironment>((proposal.voting_end + 1) as u64);
+        set_block_timestamp::<ink::env::DefaultEnvironment>(proposal.voting_end + 1);
         contract.update_proposal_status(proposal_id).unwrap();
         
         // Try to execute before execution time
@@ -416,7 +434,7 @@ mod tests {
         
         // Advance to execution time
         let proposal = contract.get_proposal(proposal_id).unwrap();
-        set_block_timestamp::<ink::env::DefaultEnvironment>((proposal.execution_time + 1) as u64);
+        set_block_timestamp::<ink::env::DefaultEnvironment>(proposal.execution_time + 1);
         
         // Execute should succeed
         let result = contract.execute_proposal(proposal_id);
@@ -436,9 +454,12 @@ mod tests {
         let (title, description, proposal_type, governance_params, _) = create_test_proposal_params();
         let voting_options = VotingOptions {
             options: (1..=10).map(|i| format!("Option {}", i)).collect(),
+            payloads: vec![],
+            actions: vec![],
+            abstain_index: None,
         };
         
-        let result = contract.create_proposal(title, description, proposal_type, governance_params, voting_options);
+        let result = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]);
         assert!(result.is_ok());
         
         let proposal = contract.get_proposal(1).unwrap();
@@ -480,14 +501,18 @@ mod tests {
         governance_params.voting_period = VotingPeriod::ThirtyDays;
         governance_params.execution_delay = ExecutionDelay::SevenDays;
         
-        // Set block timestamp near u32::MAX
-        set_block_timestamp::<ink::env::DefaultEnvironment>(u32::MAX as u64 - 1000);
+        // Near u32::MAX the deadlines no longer wrap, since times are u64 milliseconds
+        let now = Timestamp::from(u32::MAX) - 1000;
+        set_block_timestamp::<ink::env::DefaultEnvironment>(now);
+        let proposal_id = contract.create_proposal(title.clone(), description.clone(), proposal_type.clone(), governance_params.clone(), voting_options.clone(), vec![]).unwrap();
+        let proposal = contract.get_proposal(proposal_id).unwrap();
+        assert_eq!(proposal.voting_end, now + 30 * MILLIS_PER_DAY);
+        assert_eq!(proposal.execution_time, proposal.voting_end + 7 * MILLIS_PER_DAY);
         
-        let result = contract.create_proposal(title, description, proposal_type, governance_params, voting_options);
-        // Should either succeed or fail gracefully with InvalidProposal
-        if result.is_err() {
-            assert_eq!(result.unwrap_err(), crate::errors::Error::InvalidProposal);
-        }
+        // Near Timestamp::MAX the checked additions reject the proposal instead of wrapping
+        set_block_timestamp::<ink::env::DefaultEnvironment>(Timestamp::MAX - 1000);
+        let result = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]);
+        assert_eq!(result.unwrap_err(), crate::errors::Error::InvalidProposal);
     }
 
     #[ink::test]
@@ -521,18 +546,21 @@ mod tests {
         
         // Create proposal
         let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
-        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options).unwrap();
+        let proposal_id = contract.create_proposal(title.clone(), description.clone(), proposal_type.clone(), governance_params.clone(), voting_options.clone(), vec![]).unwrap();
         
         // Test quorum with no registered voters (0 votes needed, so 0 votes meets quorum)
         let has_quorum = contract.has_reached_quorum(proposal_id).unwrap();
         assert!(has_quorum);
         
-        // Register one voter
+        // Register one voter and open a proposal they are counted for
         contract.register_voter().unwrap();
+        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
         
-        // Test quorum with 10% threshold and 1 voter (should need 0.1 votes, rounded down to 0)
-        let has_quorum = contract.has_reached_quorum(proposal_id).unwrap();
-        assert!(has_quorum); // 0 votes needed, so any votes meet quorum
+        // Test quorum with 10% threshold and 1 voter (0.1 votes, rounded up to 1)
+        assert!(!contract.has_reached_quorum(proposal_id).unwrap());
+        assert_eq!(contract.get_proposal_results(proposal_id).unwrap().3, 1);
+        contract.vote(proposal_id, VoteChoice { option_index: 0, option_text: "Yes".to_string() }).unwrap();
+        assert!(contract.has_reached_quorum(proposal_id).unwrap());
     }
 
     #[ink::test]
@@ -544,7 +572,7 @@ mod tests {
         
         // Create proposal
         let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
-        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options).unwrap();
+        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
         
         // Test with no votes
         let winner = contract.get_winning_option(proposal_id).unwrap();
@@ -566,4 +594,3972 @@ mod tests {
         assert_eq!(vote_count, 1);
     }
 
+    #[ink::test]
+    fn endorsement_flow() {
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        contract.register_voter().unwrap();
+        set_caller(accounts.bob);
+        contract.register_voter().unwrap();
+        
+        set_caller(accounts.alice);
+        let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
+        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
+        
+        // Both voters endorse
+        contract.endorse_proposal(proposal_id).unwrap();
+        set_caller(accounts.bob);
+        contract.endorse_proposal(proposal_id).unwrap();
+        
+        let proposal = contract.get_proposal(proposal_id).unwrap();
+        assert_eq!(proposal.endorsement_count, 2);
+        assert_eq!(contract.get_endorsements(proposal_id, 0, 10).unwrap(), vec![accounts.alice, accounts.bob]);
+        
+        // Endorsing does not consume the vote
+        assert_eq!(proposal.total_voters, 0);
+        
+        // Double endorsement is rejected
+        let result = contract.endorse_proposal(proposal_id);
+        assert_eq!(result.unwrap_err(), crate::errors::Error::AlreadyEndorsed);
+    }
+
+    #[ink::test]
+    fn un_endorse_removes_endorsement() {
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        contract.register_voter().unwrap();
+        set_caller(accounts.bob);
+        contract.register_voter().unwrap();
+        
+        set_caller(accounts.alice);
+        let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
+        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
+        
+        contract.endorse_proposal(proposal_id).unwrap();
+        set_caller(accounts.bob);
+        contract.endorse_proposal(proposal_id).unwrap();
+        
+        // Alice withdraws, Bob takes her slot in the index
+        set_caller(accounts.alice);
+        contract.un_endorse(proposal_id).unwrap();
+        assert_eq!(contract.get_proposal(proposal_id).unwrap().endorsement_count, 1);
+        assert_eq!(contract.get_endorsements(proposal_id, 0, 10).unwrap(), vec![accounts.bob]);
+        
+        // Withdrawing twice fails
+        let result = contract.un_endorse(proposal_id);
+        assert_eq!(result.unwrap_err(), crate::errors::Error::NotEndorsed);
+        
+        // Alice can endorse again
+        contract.endorse_proposal(proposal_id).unwrap();
+        assert_eq!(contract.get_endorsements(proposal_id, 0, 10).unwrap(), vec![accounts.bob, accounts.alice]);
+    }
+
+    #[ink::test]
+    fn unregistered_account_cannot_endorse() {
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
+        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
+        
+        let result = contract.endorse_proposal(proposal_id);
+        assert_eq!(result.unwrap_err(), crate::errors::Error::NotRegistered);
+    }
+
+    #[ink::test]
+    fn tenure_weighting_favours_older_members() {
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        contract.set_tenure_weight_per_day(1).unwrap();
+        
+        // Alice registers at day 0
+        contract.register_voter().unwrap();
+        
+        // Bob registers ten days later
+        set_block_timestamp::<ink::env::DefaultEnvironment>(10 * MILLIS_PER_DAY);
+        set_caller(accounts.bob);
+        contract.register_voter().unwrap();
+        
+        let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
+        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
+        
+        set_caller(accounts.alice);
+        contract.vote(proposal_id, VoteChoice { option_index: 0, option_text: "Yes".to_string() }).unwrap();
+        set_caller(accounts.bob);
+        contract.vote(proposal_id, VoteChoice { option_index: 1, option_text: "No".to_string() }).unwrap();
+        
+        assert_eq!(contract.get_user_vote(proposal_id, accounts.alice).unwrap().weight, 11);
+        assert_eq!(contract.get_user_vote(proposal_id, accounts.bob).unwrap().weight, 1);
+        
+        let proposal = contract.get_proposal(proposal_id).unwrap();
+        assert_eq!(proposal.vote_counts, vec![11, 1]);
+    }
+
+    #[ink::test]
+    fn only_owner_sets_tenure_weight() {
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        
+        set_caller(accounts.bob);
+        let result = contract.set_tenure_weight_per_day(5);
+        assert_eq!(result.unwrap_err(), crate::errors::Error::NotAuthorized);
+        assert_eq!(contract.get_tenure_weight_per_day(), 0);
+    }
+
+    #[ink::test]
+    fn watchlist_add_and_remove() {
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
+        let first = contract.create_proposal(title.clone(), description.clone(), proposal_type.clone(), governance_params.clone(), voting_options.clone(), vec![]).unwrap();
+        let second = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
+        
+        set_caller(accounts.bob);
+        contract.watch_proposal(first).unwrap();
+        contract.watch_proposal(second).unwrap();
+        assert_eq!(contract.watch_proposal(first).unwrap_err(), crate::errors::Error::AlreadyWatching);
+        assert_eq!(contract.watch_proposal(999).unwrap_err(), crate::errors::Error::ProposalNotFound);
+        
+        let ids: Vec<u32> = contract.get_watchlist(accounts.bob, 0, 10).iter().map(|summary| summary.id).collect();
+        assert_eq!(ids, vec![first, second]);
+        
+        contract.unwatch_proposal(first).unwrap();
+        let ids: Vec<u32> = contract.get_watchlist(accounts.bob, 0, 10).iter().map(|summary| summary.id).collect();
+        assert_eq!(ids, vec![second]);
+        assert_eq!(contract.unwatch_proposal(first).unwrap_err(), crate::errors::Error::NotWatching);
+        
+        // Watchlists are per account
+        assert!(contract.get_watchlist(accounts.alice, 0, 10).is_empty());
+    }
+
+    #[ink::test]
+    fn watchlist_cap_enforced() {
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
+        for _ in 0..=MAX_WATCHLIST_SIZE {
+            let proposal_id = contract.create_proposal(title.clone(), description.clone(), proposal_type.clone(), governance_params.clone(), voting_options.clone(), vec![]).unwrap();
+            if (proposal_id as usize) <= MAX_WATCHLIST_SIZE {
+                contract.watch_proposal(proposal_id).unwrap();
+            }
+        }
+        
+        let result = contract.watch_proposal(MAX_WATCHLIST_SIZE as u32 + 1);
+        assert_eq!(result.unwrap_err(), crate::errors::Error::WatchlistFull);
+        
+        // Pagination over the full list
+        assert_eq!(contract.get_watchlist(accounts.alice, 0, 100).len(), MAX_PAGE_SIZE as usize);
+        assert_eq!(contract.get_watchlist(accounts.alice, 90, 50).len(), 10);
+    }
+
+    #[ink::test]
+    fn watchlist_reflects_live_status() {
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        contract.register_voter().unwrap();
+        let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
+        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
+        contract.watch_proposal(proposal_id).unwrap();
+        
+        assert_eq!(contract.get_watchlist(accounts.alice, 0, 10)[0].status, ProposalStatus::Active);
+        
+        contract.vote(proposal_id, VoteChoice { option_index: 0, option_text: "Yes".to_string() }).unwrap();
+        let proposal = contract.get_proposal(proposal_id).unwrap();
+        set_block_timestamp::<ink::env::DefaultEnvironment>(proposal.voting_end + 1);
+        contract.update_proposal_status(proposal_id).unwrap();
+        
+        // Terminal or not, the proposal stays on the list with its current status
+        let summary = &contract.get_watchlist(accounts.alice, 0, 10)[0];
+        assert_eq!(summary.status, ProposalStatus::Passed);
+        assert_eq!(summary.total_voters, 1);
+    }
+
+    #[ink::test]
+    fn whale_fails_distinct_participant_minimum() {
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        contract.set_tenure_weight_per_day(10).unwrap();
+        contract.set_min_distinct_participants(2).unwrap();
+        
+        // Alice is a long-standing member, Bob and Charlie join later
+        contract.register_voter().unwrap();
+        set_block_timestamp::<ink::env::DefaultEnvironment>(100 * MILLIS_PER_DAY);
+        set_caller(accounts.bob);
+        contract.register_voter().unwrap();
+        set_caller(accounts.charlie);
+        contract.register_voter().unwrap();
+        
+        set_caller(accounts.alice);
+        let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
+        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
+        contract.vote(proposal_id, VoteChoice { option_index: 0, option_text: "Yes".to_string() }).unwrap();
+        
+        // The whale alone satisfies the quorum...
+        assert!(contract.has_reached_quorum(proposal_id).unwrap());
+        assert_eq!(contract.get_proposal(proposal_id).unwrap().vote_counts[0], 1001);
+        
+        // ...but one participant is below the distinct minimum
+        let proposal = contract.get_proposal(proposal_id).unwrap();
+        set_block_timestamp::<ink::env::DefaultEnvironment>(proposal.voting_end + 1);
+        assert_eq!(contract.update_proposal_status(proposal_id).unwrap(), ProposalStatus::Rejected);
+    }
+
+    #[ink::test]
+    fn reset_votes_clears_tally() {
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        contract.register_voter().unwrap();
+        set_caller(accounts.bob);
+        contract.register_voter().unwrap();
+        
+        set_caller(accounts.alice);
+        let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
+        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
+        
+        contract.vote(proposal_id, VoteChoice { option_index: 0, option_text: "Yes".to_string() }).unwrap();
+        set_caller(accounts.bob);
+        contract.vote(proposal_id, VoteChoice { option_index: 1, option_text: "No".to_string() }).unwrap();
+        
+        // Only the owner may reset
+        let result = contract.reset_votes(proposal_id);
+        assert_eq!(result.unwrap_err(), crate::errors::Error::NotAuthorized);
+        
+        set_caller(accounts.alice);
+        contract.reset_votes(proposal_id).unwrap();
+        
+        let proposal = contract.get_proposal(proposal_id).unwrap();
+        assert_eq!(proposal.vote_counts, vec![0, 0]);
+        assert_eq!(proposal.total_voters, 0);
+        assert!(contract.get_user_vote(proposal_id, accounts.alice).is_none());
+        assert!(contract.get_user_vote(proposal_id, accounts.bob).is_none());
+        
+        // Both voters can vote again
+        contract.vote(proposal_id, VoteChoice { option_index: 1, option_text: "No".to_string() }).unwrap();
+        set_caller(accounts.bob);
+        contract.vote(proposal_id, VoteChoice { option_index: 1, option_text: "No".to_string() }).unwrap();
+        
+        let proposal = contract.get_proposal(proposal_id).unwrap();
+        assert_eq!(proposal.vote_counts, vec![0, 2]);
+        assert_eq!(proposal.total_voters, 2);
+    }
+
+    #[ink::test]
+    fn execution_delay_override_supersedes_enum() {
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        let (title, description, proposal_type, mut governance_params, voting_options) = create_test_proposal_params();
+        
+        governance_params.execution_delay_override = Some(36 * 60 * 60 * 1000);
+        let proposal_id = contract.create_proposal(title.clone(), description.clone(), proposal_type.clone(), governance_params.clone(), voting_options.clone(), vec![]).unwrap();
+        
+        let proposal = contract.get_proposal(proposal_id).unwrap();
+        assert_eq!(proposal.execution_time, proposal.voting_end + 36 * 60 * 60 * 1000);
+        
+        // Overrides beyond the maximum are rejected
+        governance_params.execution_delay_override = Some(MAX_EXECUTION_DELAY + 1);
+        let result = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]);
+        assert_eq!(result.unwrap_err(), crate::errors::Error::InvalidProposal);
+    }
+
+    #[ink::test]
+    fn pending_actions_queue() {
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        contract.register_voter().unwrap();
+        
+        let (title, description, proposal_type, mut governance_params, voting_options) = create_test_proposal_params();
+        governance_params.voting_period = VotingPeriod::ThreeDays;
+        governance_params.execution_delay = ExecutionDelay::Immediately;
+        let first = contract.create_proposal(title.clone(), description.clone(), proposal_type.clone(), governance_params.clone(), voting_options.clone(), vec![]).unwrap();
+        let second = contract.create_proposal(title.clone(), description.clone(), proposal_type.clone(), governance_params.clone(), voting_options.clone(), vec![]).unwrap();
+        governance_params.voting_period = VotingPeriod::SevenDays;
+        let third = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
+        
+        contract.vote(first, VoteChoice { option_index: 0, option_text: "Yes".to_string() }).unwrap();
+        contract.vote(second, VoteChoice { option_index: 0, option_text: "Yes".to_string() }).unwrap();
+        assert!(contract.get_pending_actions(10).is_empty());
+        
+        // After the short voting period, finalize one of the two
+        let voting_end = contract.get_proposal(first).unwrap().voting_end;
+        set_block_timestamp::<ink::env::DefaultEnvironment>(voting_end + 1);
+        contract.update_proposal_status(first).unwrap();
+        
+        // Finalizations are reported before executions
+        assert_eq!(contract.get_pending_actions(10), vec![
+            PendingAction::NeedsFinalization(second),
+            PendingAction::ReadyToExecute(first),
+        ]);
+        assert_eq!(contract.get_pending_actions(1), vec![PendingAction::NeedsFinalization(second)]);
+        
+        // A keeper processes the queue
+        contract.update_proposal_status(second).unwrap();
+        contract.execute_proposal(first).unwrap();
+        assert_eq!(contract.get_pending_actions(10), vec![PendingAction::ReadyToExecute(second)]);
+        
+        // Close to its deadline the remaining active proposal shows up as expiring
+        let deadline = contract.get_proposal(third).unwrap().voting_end;
+        set_block_timestamp::<ink::env::DefaultEnvironment>(deadline - EXPIRING_SOON_WINDOW / 2);
+        assert_eq!(contract.get_pending_actions(10), vec![
+            PendingAction::ReadyToExecute(second),
+            PendingAction::ExpiringSoon { proposal_id: third, deadline },
+        ]);
+    }
+
+    #[ink::test]
+    fn validate_proposal_params_matches_create() {
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
+        
+        // Valid parameters pass without creating anything
+        assert!(contract.validate_proposal_params(title.clone(), description.clone(), proposal_type.clone(), voting_options.clone(), governance_params.clone()).is_ok());
+        assert_eq!(contract.get_proposal_count(), 0);
+        
+        let duplicate_options = VotingOptions {
+            options: vec!["Yes".to_string(), "Yes".to_string()],
+            payloads: vec![],
+            actions: vec![],
+            abstain_index: None,
+        };
+        let long_title = "x".repeat(MAX_TITLE_LENGTH + 1);
+        let mut bad_override = governance_params.clone();
+        bad_override.execution_delay_override = Some(MAX_EXECUTION_DELAY + 1);
+        
+        let cases = vec![
+            (title.clone(), description.clone(), VotingOptions { options: vec![], payloads: vec![], actions: vec![], abstain_index: None }, governance_params.clone()),
+            (title.clone(), description.clone(), duplicate_options, governance_params.clone()),
+            (long_title, description.clone(), voting_options.clone(), governance_params.clone()),
+            ("  ".to_string(), description.clone(), voting_options.clone(), governance_params.clone()),
+            (title.clone(), description.clone(), voting_options.clone(), bad_override),
+        ];
+        
+        for (case_title, case_description, case_options, case_params) in cases {
+            let validation = contract.validate_proposal_params(case_title.clone(), case_description.clone(), proposal_type.clone(), case_options.clone(), case_params.clone());
+            let creation = contract.create_proposal(case_title, case_description, proposal_type.clone(), case_params, case_options, vec![]);
+            assert!(validation.is_err());
+            assert_eq!(validation.unwrap_err(), creation.unwrap_err());
+        }
+        
+        // Timing overflow is reported the same way
+        set_block_timestamp::<ink::env::DefaultEnvironment>(Timestamp::MAX - 1000);
+        let validation = contract.validate_proposal_params(title.clone(), description.clone(), proposal_type.clone(), voting_options.clone(), governance_params.clone());
+        let creation = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]);
+        assert_eq!(validation.unwrap_err(), creation.unwrap_err());
+    }
+
+    #[ink::test]
+    fn duration_bounds_per_proposal_type() {
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        let (title, description, _, mut governance_params, voting_options) = create_test_proposal_params();
+        
+        // Defaults preserve current behaviour
+        assert_eq!(contract.get_duration_bounds(ProposalType::Treasury), DurationBounds::default());
+        governance_params.voting_period = VotingPeriod::ThreeDays;
+        governance_params.execution_delay = ExecutionDelay::Immediately;
+        let in_flight = contract.create_proposal(title.clone(), description.clone(), ProposalType::Treasury, governance_params.clone(), voting_options.clone(), vec![]).unwrap();
+        let in_flight_end = contract.get_proposal(in_flight).unwrap().voting_end;
+        
+        contract.set_duration_bounds(ProposalType::Treasury, DurationBounds {
+            min_voting: 5 * MILLIS_PER_DAY,
+            max_voting: 14 * MILLIS_PER_DAY,
+            min_delay: MILLIS_PER_DAY,
+        }).unwrap();
+        contract.set_duration_bounds(ProposalType::Technical, DurationBounds {
+            min_voting: MILLIS_PER_DAY,
+            max_voting: Timestamp::MAX,
+            min_delay: 0,
+        }).unwrap();
+        
+        // Treasury: too short, too long, delay too short
+        let result = contract.create_proposal(title.clone(), description.clone(), ProposalType::Treasury, governance_params.clone(), voting_options.clone(), vec![]);
+        assert_eq!(result.unwrap_err(), crate::errors::Error::VotingPeriodTooShort);
+        
+        governance_params.voting_period = VotingPeriod::ThirtyDays;
+        let result = contract.create_proposal(title.clone(), description.clone(), ProposalType::Treasury, governance_params.clone(), voting_options.clone(), vec![]);
+        assert_eq!(result.unwrap_err(), crate::errors::Error::VotingPeriodTooLong);
+        
+        governance_params.voting_period = VotingPeriod::SevenDays;
+        let result = contract.create_proposal(title.clone(), description.clone(), ProposalType::Treasury, governance_params.clone(), voting_options.clone(), vec![]);
+        assert_eq!(result.unwrap_err(), crate::errors::Error::ExecutionDelayTooShort);
+        
+        governance_params.execution_delay = ExecutionDelay::OneDay;
+        assert!(contract.create_proposal(title.clone(), description.clone(), ProposalType::Treasury, governance_params.clone(), voting_options.clone(), vec![]).is_ok());
+        
+        // Technical proposals may stay short
+        governance_params.voting_period = VotingPeriod::ThreeDays;
+        governance_params.execution_delay = ExecutionDelay::Immediately;
+        assert!(contract.create_proposal(title.clone(), description.clone(), ProposalType::Technical, governance_params.clone(), voting_options.clone(), vec![]).is_ok());
+        
+        // Dry-run validation consults the same bounds
+        let result = contract.validate_proposal_params(title, description, ProposalType::Treasury, voting_options, governance_params);
+        assert_eq!(result.unwrap_err(), crate::errors::Error::VotingPeriodTooShort);
+        
+        // Proposals created before the change keep their timeline
+        assert_eq!(contract.get_proposal(in_flight).unwrap().voting_end, in_flight_end);
+        
+        // Inverted bounds and non-owners are rejected
+        let result = contract.set_duration_bounds(ProposalType::Other, DurationBounds { min_voting: 10, max_voting: 5, min_delay: 0 });
+        assert_eq!(result.unwrap_err(), crate::errors::Error::InvalidProposal);
+        set_caller(accounts.bob);
+        let result = contract.set_duration_bounds(ProposalType::Other, DurationBounds::default());
+        assert_eq!(result.unwrap_err(), crate::errors::Error::NotAuthorized);
+    }
+
+    #[ink::test]
+    fn far_future_timestamp_rejected() {
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        contract.set_max_reasonable_timestamp(1_000_000).unwrap();
+        
+        let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
+        set_block_timestamp::<ink::env::DefaultEnvironment>(1_000_000);
+        assert!(contract.create_proposal(title.clone(), description.clone(), proposal_type.clone(), governance_params.clone(), voting_options.clone(), vec![]).is_ok());
+        
+        set_block_timestamp::<ink::env::DefaultEnvironment>(2_000_000);
+        let result = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]);
+        assert_eq!(result.unwrap_err(), crate::errors::Error::InvalidProposal);
+    }
+
+    #[ink::test]
+    fn global_option_tally_sums_matching_labels() {
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        contract.register_voter().unwrap();
+        set_caller(accounts.bob);
+        contract.register_voter().unwrap();
+        
+        let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
+        let first = contract.create_proposal(title.clone(), description.clone(), proposal_type.clone(), governance_params.clone(), voting_options, vec![]).unwrap();
+        let differently_cased = VotingOptions {
+            options: vec!["yes".to_string(), " NO ".to_string(), "Abstain".to_string()],
+            payloads: vec![],
+            actions: vec![],
+            abstain_index: None,
+        };
+        let second = contract.create_proposal(title, description, proposal_type, governance_params, differently_cased, vec![]).unwrap();
+        
+        set_caller(accounts.alice);
+        contract.vote(first, VoteChoice { option_index: 0, option_text: "Yes".to_string() }).unwrap();
+        contract.vote(second, VoteChoice { option_index: 0, option_text: "yes".to_string() }).unwrap();
+        set_caller(accounts.bob);
+        contract.vote(first, VoteChoice { option_index: 1, option_text: "No".to_string() }).unwrap();
+        contract.vote(second, VoteChoice { option_index: 0, option_text: "yes".to_string() }).unwrap();
+        
+        assert_eq!(contract.get_global_option_tally(), vec![
+            ("yes".to_string(), 3),
+            ("no".to_string(), 1),
+            ("abstain".to_string(), 0),
+        ]);
+    }
+
+    #[ink::test]
+    fn option_payloads_round_trip() {
+        use scale::{Decode, Encode};
+        
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        contract.register_voter().unwrap();
+        
+        let (title, description, proposal_type, governance_params, _) = create_test_proposal_params();
+        let candidates = VotingOptions {
+            options: vec!["Bob".to_string(), "Charlie".to_string()],
+            payloads: vec![accounts.bob.encode(), accounts.charlie.encode()],
+            actions: vec![],
+            abstain_index: None,
+        };
+        let proposal_id = contract.create_proposal(title.clone(), description.clone(), proposal_type.clone(), governance_params.clone(), candidates, vec![]).unwrap();
+        
+        contract.vote(proposal_id, VoteChoice { option_index: 1, option_text: "Charlie".to_string() }).unwrap();
+        
+        // The winning option's payload decodes back to the candidate address
+        let payload = contract.get_option_payload(proposal_id, 1).unwrap().unwrap();
+        let candidate = ink::primitives::H160::decode(&mut &payload[..]).unwrap();
+        assert_eq!(candidate, accounts.charlie);
+        assert_eq!(contract.get_option_payload(proposal_id, 2).unwrap_err(), crate::errors::Error::InvalidProposal);
+        
+        // Payload count must match the option count and each payload is size-bounded
+        let mismatched = VotingOptions {
+            options: vec!["Bob".to_string(), "Charlie".to_string()],
+            payloads: vec![accounts.bob.encode()],
+            actions: vec![],
+            abstain_index: None,
+        };
+        let result = contract.create_proposal(title.clone(), description.clone(), proposal_type.clone(), governance_params.clone(), mismatched, vec![]);
+        assert_eq!(result.unwrap_err(), crate::errors::Error::InvalidProposal);
+        
+        let oversized = VotingOptions {
+            options: vec!["Big".to_string()],
+            payloads: vec![vec![0u8; MAX_OPTION_PAYLOAD_SIZE + 1]],
+            actions: vec![],
+            abstain_index: None,
+        };
+        let result = contract.create_proposal(title, description, proposal_type, governance_params, oversized, vec![]);
+        assert_eq!(result.unwrap_err(), crate::errors::Error::InvalidProposal);
+    }
+
+    #[ink::test]
+    fn quorum_projection_with_expected_turnout() {
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        let voters = [accounts.alice, accounts.bob, accounts.charlie, accounts.django, accounts.eve, accounts.frank];
+        for voter in voters {
+            set_caller(voter);
+            contract.register_voter().unwrap();
+        }
+        
+        // A 25% threshold over six registered voters requires two votes
+        set_caller(accounts.alice);
+        let (title, description, proposal_type, mut governance_params, voting_options) = create_test_proposal_params();
+        governance_params.quorum_threshold = QuorumThreshold::TwentyFive;
+        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
+        
+        assert!(!contract.has_reached_quorum(proposal_id).unwrap());
+        assert!(!contract.quorum_projection(proposal_id, 1).unwrap());
+        assert!(contract.quorum_projection(proposal_id, 2).unwrap());
+        
+        // Projection is read-only
+        assert_eq!(contract.get_proposal(proposal_id).unwrap().total_voters, 0);
+        
+        assert_eq!(contract.quorum_projection(999, 1).unwrap_err(), crate::errors::Error::ProposalNotFound);
+    }
+
+    #[ink::test]
+    fn scheduled_activation_gates_voting() {
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        contract.register_voter().unwrap();
+        
+        let activation = 2 * MILLIS_PER_DAY;
+        let (title, description, proposal_type, mut governance_params, voting_options) = create_test_proposal_params();
+        governance_params.scheduled_activation = Some(activation);
+        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
+        
+        // The voting clock starts at activation
+        let proposal = contract.get_proposal(proposal_id).unwrap();
+        assert_eq!(proposal.status, ProposalStatus::Scheduled);
+        assert_eq!(proposal.voting_end, activation + 7 * MILLIS_PER_DAY);
+        
+        // Voting and finalization are blocked before activation
+        let vote_choice = VoteChoice { option_index: 0, option_text: "Yes".to_string() };
+        assert_eq!(contract.vote(proposal_id, vote_choice.clone()).unwrap_err(), crate::errors::Error::ProposalNotActive);
+        assert_eq!(contract.update_proposal_status(proposal_id).unwrap_err(), crate::errors::Error::ProposalNotReadyForExecution);
+        
+        // Once the scheduled time is reached the proposal behaves as active
+        set_block_timestamp::<ink::env::DefaultEnvironment>(activation);
+        contract.vote(proposal_id, vote_choice).unwrap();
+        
+        let proposal = contract.get_proposal(proposal_id).unwrap();
+        assert_eq!(proposal.status, ProposalStatus::Active);
+        assert_eq!(proposal.vote_counts[0], 1);
+    }
+
+    #[ink::test]
+    fn suspension_extends_timeline() {
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        contract.register_voter().unwrap();
+        contract.set_guardian(accounts.bob).unwrap();
+        
+        let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
+        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
+        let original = contract.get_proposal(proposal_id).unwrap();
+        
+        // Only the guardian can suspend
+        assert_eq!(contract.suspend_proposal(proposal_id).unwrap_err(), crate::errors::Error::NotAuthorized);
+        
+        // First cycle: suspended for one day
+        set_caller(accounts.bob);
+        set_block_timestamp::<ink::env::DefaultEnvironment>(MILLIS_PER_DAY);
+        contract.suspend_proposal(proposal_id).unwrap();
+        assert_eq!(contract.get_proposal(proposal_id).unwrap().status, ProposalStatus::Suspended);
+        
+        // Voting is blocked while suspended
+        set_caller(accounts.alice);
+        let vote_choice = VoteChoice { option_index: 0, option_text: "Yes".to_string() };
+        assert_eq!(contract.vote(proposal_id, vote_choice.clone()).unwrap_err(), crate::errors::Error::ProposalNotActive);
+        
+        set_caller(accounts.bob);
+        set_block_timestamp::<ink::env::DefaultEnvironment>(2 * MILLIS_PER_DAY);
+        contract.resume_proposal(proposal_id).unwrap();
+        
+        // Second cycle spans the original deadline and lasts three days
+        set_block_timestamp::<ink::env::DefaultEnvironment>(original.voting_end - MILLIS_PER_DAY);
+        contract.suspend_proposal(proposal_id).unwrap();
+        
+        // Finalization is blocked and the proposal stays out of the keeper queue
+        set_block_timestamp::<ink::env::DefaultEnvironment>(original.voting_end + MILLIS_PER_DAY);
+        assert_eq!(contract.update_proposal_status(proposal_id).unwrap_err(), crate::errors::Error::ProposalNotReadyForExecution);
+        assert!(contract.get_pending_actions(10).is_empty());
+        
+        set_block_timestamp::<ink::env::DefaultEnvironment>(original.voting_end + 2 * MILLIS_PER_DAY);
+        contract.resume_proposal(proposal_id).unwrap();
+        
+        // Both suspensions are added to the deadlines
+        let proposal = contract.get_proposal(proposal_id).unwrap();
+        assert_eq!(proposal.status, ProposalStatus::Active);
+        assert_eq!(proposal.voting_end, original.voting_end + 4 * MILLIS_PER_DAY);
+        assert_eq!(proposal.execution_time, original.execution_time + 4 * MILLIS_PER_DAY);
+        
+        // The time lost to suspension is still available for voting
+        set_caller(accounts.alice);
+        contract.vote(proposal_id, vote_choice).unwrap();
+        
+        // Resuming an active proposal fails
+        set_caller(accounts.bob);
+        assert_eq!(contract.resume_proposal(proposal_id).unwrap_err(), crate::errors::Error::ProposalNotActive);
+    }
+
+    #[ink::test]
+    fn timestamp_beyond_u32_is_kept_in_full() {
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        contract.register_voter().unwrap();
+        
+        // Millisecond timestamps pass u32::MAX within weeks of genesis and must not be truncated
+        let now = Timestamp::from(u32::MAX) + 10;
+        set_block_timestamp::<ink::env::DefaultEnvironment>(now);
+        
+        let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
+        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
+        let proposal = contract.get_proposal(proposal_id).unwrap();
+        assert_eq!(proposal.created_at, now);
+        assert_eq!(proposal.voting_end, now + 7 * MILLIS_PER_DAY);
+        
+        contract.vote(proposal_id, VoteChoice { option_index: 0, option_text: "Yes".to_string() }).unwrap();
+        assert_eq!(contract.get_user_vote(proposal_id, accounts.alice).unwrap().timestamp, now);
+        
+        set_caller(accounts.bob);
+        contract.register_voter().unwrap();
+        assert_eq!(contract.get_registration_time(accounts.bob), Some(now));
+    }
+
+    #[ink::test]
+    fn probation_blocks_new_voters_until_boundary() {
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        contract.set_voting_probation(MILLIS_PER_DAY, true).unwrap();
+        
+        let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
+        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
+        
+        set_block_timestamp::<ink::env::DefaultEnvironment>(1000);
+        set_caller(accounts.bob);
+        contract.register_voter().unwrap();
+        
+        // Still inside the window
+        set_block_timestamp::<ink::env::DefaultEnvironment>(1000 + MILLIS_PER_DAY - 1);
+        assert_eq!(contract.can_vote(proposal_id, accounts.bob).unwrap_err(), crate::errors::Error::VoterInProbation);
+        let vote_choice = VoteChoice { option_index: 0, option_text: "Yes".to_string() };
+        assert_eq!(contract.vote(proposal_id, vote_choice.clone()).unwrap_err(), crate::errors::Error::VoterInProbation);
+        
+        // Exactly at the end of probation voting is allowed
+        set_block_timestamp::<ink::env::DefaultEnvironment>(1000 + MILLIS_PER_DAY);
+        assert!(contract.can_vote(proposal_id, accounts.bob).is_ok());
+        contract.vote(proposal_id, vote_choice).unwrap();
+        assert_eq!(contract.can_vote(proposal_id, accounts.bob).unwrap_err(), crate::errors::Error::AlreadyVoted);
+    }
+
+    #[ink::test]
+    fn probation_quorum_denominator_choice() {
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        contract.set_voting_probation(MILLIS_PER_DAY, true).unwrap();
+        contract.register_voter().unwrap();
+        
+        // Four more voters join right before the proposal opens
+        set_block_timestamp::<ink::env::DefaultEnvironment>(2 * MILLIS_PER_DAY);
+        for voter in [accounts.bob, accounts.charlie, accounts.django, accounts.eve] {
+            set_caller(voter);
+            contract.register_voter().unwrap();
+        }
+        
+        set_caller(accounts.alice);
+        let (title, description, proposal_type, mut governance_params, voting_options) = create_test_proposal_params();
+        governance_params.quorum_threshold = QuorumThreshold::TwentyFive;
+        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
+        contract.vote(proposal_id, VoteChoice { option_index: 0, option_text: "Yes".to_string() }).unwrap();
+        
+        // Counting probationary voters: 25% of five voters needs two votes
+        assert!(!contract.has_reached_quorum(proposal_id).unwrap());
+        
+        // Excluding them: only Alice is eligible and 25% of one rounds up to her vote
+        contract.set_voting_probation(MILLIS_PER_DAY, false).unwrap();
+        assert!(contract.has_reached_quorum(proposal_id).unwrap());
+        
+        // Once probation ends everyone counts again
+        set_block_timestamp::<ink::env::DefaultEnvironment>(3 * MILLIS_PER_DAY);
+        assert!(!contract.has_reached_quorum(proposal_id).unwrap());
+    }
+
+    #[ink::test]
+    fn dashboard_matches_individual_getters() {
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        contract.set_guardian(accounts.eve).unwrap();
+        contract.set_tenure_weight_per_day(2).unwrap();
+        contract.set_min_distinct_participants(1).unwrap();
+        contract.register_voter().unwrap();
+        set_caller(accounts.bob);
+        contract.register_voter().unwrap();
+        
+        // Seven proposals with a mix of outcomes
+        set_caller(accounts.alice);
+        let (title, description, proposal_type, mut governance_params, voting_options) = create_test_proposal_params();
+        governance_params.execution_delay = ExecutionDelay::Immediately;
+        let mut ids = Vec::new();
+        for _ in 0..7 {
+            ids.push(contract.create_proposal(title.clone(), description.clone(), proposal_type.clone(), governance_params.clone(), voting_options.clone(), vec![]).unwrap());
+        }
+        
+        let yes = VoteChoice { option_index: 0, option_text: "Yes".to_string() };
+        for &id in &ids[..4] {
+            contract.vote(id, yes.clone()).unwrap();
+        }
+        set_caller(accounts.eve);
+        contract.suspend_proposal(ids[6]).unwrap();
+        
+        let voting_end = contract.get_proposal(ids[0]).unwrap().voting_end;
+        set_block_timestamp::<ink::env::DefaultEnvironment>(voting_end + 1);
+        for &id in &ids[..5] {
+            contract.update_proposal_status(id).unwrap();
+        }
+        contract.execute_proposal(ids[0]).unwrap();
+        contract.execute_proposal(ids[1]).unwrap();
+        
+        let dashboard = contract.get_dashboard();
+        assert_eq!(dashboard.version, DASHBOARD_VERSION);
+        assert_eq!(dashboard.owner, accounts.alice);
+        assert_eq!(dashboard.guardian, contract.get_guardian());
+        assert_eq!(dashboard.total_voters, contract.get_total_voters());
+        assert_eq!(dashboard.proposal_count, contract.get_proposal_count());
+        assert_eq!(dashboard.tenure_weight_per_day, contract.get_tenure_weight_per_day());
+        assert_eq!(dashboard.min_distinct_participants, contract.get_min_distinct_participants());
+        assert_eq!(dashboard.voting_probation, contract.get_voting_probation().0);
+        
+        let (_, active, executed, passed, rejected) = contract.get_stats();
+        assert_eq!(dashboard.status_counts.active, active);
+        assert_eq!(dashboard.status_counts.executed, executed);
+        assert_eq!(dashboard.status_counts.passed, passed);
+        assert_eq!(dashboard.status_counts.rejected, rejected);
+        
+        // Counters agree with a brute-force recount of stored statuses
+        let mut recount = StatusCounts::default();
+        for &id in &ids {
+            recount.increment(&contract.get_proposal(id).unwrap().status);
+        }
+        assert_eq!(dashboard.status_counts, recount);
+        assert_eq!(recount.executed, 2);
+        assert_eq!(recount.passed, 2);
+        assert_eq!(recount.rejected, 1);
+        assert_eq!(recount.active, 1);
+        assert_eq!(recount.suspended, 1);
+        
+        // Open proposals exclude terminal ones and the ring buffer keeps the latest five
+        let mut open_ids = dashboard.open_proposal_ids.clone();
+        open_ids.sort();
+        assert_eq!(open_ids, vec![ids[2], ids[3], ids[5], ids[6]]);
+        assert_eq!(dashboard.recent_proposal_ids, ids[2..].to_vec());
+    }
+
+    #[ink::test]
+    fn can_create_proposal_reflects_gating() {
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        assert_eq!(contract.can_create_proposal(accounts.bob), Ok(true));
+        
+        // Pausing blocks creation
+        contract.set_paused(true).unwrap();
+        assert_eq!(contract.can_create_proposal(accounts.bob).unwrap_err(), crate::errors::Error::ContractPaused);
+        let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
+        let result = contract.create_proposal(title.clone(), description.clone(), proposal_type.clone(), governance_params.clone(), voting_options.clone(), vec![]);
+        assert_eq!(result.unwrap_err(), crate::errors::Error::ContractPaused);
+        contract.set_paused(false).unwrap();
+        
+        // Reaching the active proposal cap blocks creation
+        contract.set_max_active_proposals(2).unwrap();
+        contract.create_proposal(title.clone(), description.clone(), proposal_type.clone(), governance_params.clone(), voting_options.clone(), vec![]).unwrap();
+        assert_eq!(contract.can_create_proposal(accounts.bob), Ok(true));
+        let second = contract.create_proposal(title.clone(), description.clone(), proposal_type.clone(), governance_params.clone(), voting_options.clone(), vec![]).unwrap();
+        assert_eq!(contract.can_create_proposal(accounts.bob).unwrap_err(), crate::errors::Error::TooManyActiveProposals);
+        
+        // Finalizing one frees a slot
+        let voting_end = contract.get_proposal(second).unwrap().voting_end;
+        set_block_timestamp::<ink::env::DefaultEnvironment>(voting_end + 1);
+        contract.update_proposal_status(second).unwrap();
+        assert_eq!(contract.can_create_proposal(accounts.bob), Ok(true));
+        
+        // Lifting the cap
+        contract.set_max_active_proposals(0).unwrap();
+        assert_eq!(contract.can_create_proposal(accounts.bob), Ok(true));
+    }
+
+    #[ink::test]
+    fn co_authors_validated_and_listed() {
+        use scale::Encode;
+        
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        for voter in [accounts.bob, accounts.charlie] {
+            set_caller(voter);
+            contract.register_voter().unwrap();
+        }
+        
+        set_caller(accounts.alice);
+        let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
+        
+        // Duplicates and the proposer are dropped
+        let co_authors = vec![accounts.bob, accounts.alice, accounts.charlie, accounts.bob];
+        let proposal_id = contract.create_proposal(title.clone(), description.clone(), proposal_type.clone(), governance_params.clone(), voting_options.clone(), co_authors).unwrap();
+        assert_eq!(contract.get_authors(proposal_id).unwrap(), vec![accounts.alice, accounts.bob, accounts.charlie]);
+        
+        let proposal = contract.get_proposal(proposal_id).unwrap();
+        assert!(proposal.is_author(accounts.charlie));
+        assert!(!proposal.is_author(accounts.django));
+        
+        // Co-authors are part of the creation event
+        let event = ink::env::test::recorded_events().into_iter().last().unwrap();
+        assert_eq!(event.data, (proposal_id, accounts.alice, title.clone(), vec![accounts.bob, accounts.charlie]).encode());
+        
+        // Unregistered co-authors are rejected
+        let result = contract.create_proposal(title.clone(), description.clone(), proposal_type.clone(), governance_params.clone(), voting_options.clone(), vec![accounts.django]);
+        assert_eq!(result.unwrap_err(), crate::errors::Error::NotRegistered);
+        
+        // At most MAX_CO_AUTHORS distinct co-authors
+        let many: Vec<ink::primitives::H160> = (0..=MAX_CO_AUTHORS as u8).map(|i| ink::primitives::H160::from([i + 100; 20])).collect();
+        for &account in &many {
+            set_caller(account);
+            contract.register_voter().unwrap();
+        }
+        set_caller(accounts.alice);
+        let result = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, many);
+        assert_eq!(result.unwrap_err(), crate::errors::Error::InvalidProposal);
+    }
+
+    #[ink::test]
+    fn execution_records_executor_and_time() {
+        use scale::Encode;
+        
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        contract.register_voter().unwrap();
+        let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
+        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
+        contract.vote(proposal_id, VoteChoice { option_index: 0, option_text: "Yes".to_string() }).unwrap();
+        
+        let proposal = contract.get_proposal(proposal_id).unwrap();
+        set_block_timestamp::<ink::env::DefaultEnvironment>(proposal.voting_end + 1);
+        contract.update_proposal_status(proposal_id).unwrap();
+        assert_eq!(contract.get_execution_record(proposal_id).unwrap(), None);
+        
+        // Anyone may execute; the executor is recorded
+        let executed_at = proposal.execution_time + 5;
+        set_block_timestamp::<ink::env::DefaultEnvironment>(executed_at);
+        set_caller(accounts.bob);
+        contract.execute_proposal(proposal_id).unwrap();
+        
+        assert_eq!(contract.get_execution_record(proposal_id).unwrap(), Some((accounts.bob, executed_at)));
+        let proposal = contract.get_proposal(proposal_id).unwrap();
+        assert_eq!(proposal.executed_by, Some(accounts.bob));
+        assert_eq!(proposal.executed_at, Some(executed_at));
+        
+        let event = ink::env::test::recorded_events().into_iter().last().unwrap();
+        assert_eq!(event.data, (proposal_id, ProposalStatus::Executed, Some(accounts.bob), contract.get_ballot_commitment(proposal_id).unwrap().commitment).encode());
+    }
+
+    #[ink::test]
+    fn governance_proposal_applies_parameter_change() {
+        use scale::Encode;
+        
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        contract.register_voter().unwrap();
+        assert_eq!(contract.get_default_quorum_threshold(), QuorumThreshold::Ten);
+        
+        let (title, description, _, governance_params, _) = create_test_proposal_params();
+        let voting_options = VotingOptions {
+            options: vec!["Set quorum to 25%".to_string(), "Keep".to_string()],
+            payloads: vec![ParameterChange { action: GovernanceAction::SetDefaultQuorum(QuorumThreshold::TwentyFive), sunset_after: None }.encode(), vec![]],
+            actions: vec![],
+            abstain_index: None,
+        };
+        let proposal_id = contract.create_proposal(title, description, ProposalType::Governance, governance_params, voting_options, vec![]).unwrap();
+        contract.vote(proposal_id, VoteChoice { option_index: 0, option_text: "Set quorum to 25%".to_string() }).unwrap();
+        
+        let proposal = contract.get_proposal(proposal_id).unwrap();
+        set_block_timestamp::<ink::env::DefaultEnvironment>(proposal.voting_end + 1);
+        assert_eq!(contract.update_proposal_status(proposal_id).unwrap(), ProposalStatus::Passed);
+        
+        set_block_timestamp::<ink::env::DefaultEnvironment>(proposal.execution_time);
+        contract.execute_proposal(proposal_id).unwrap();
+        assert_eq!(contract.get_default_quorum_threshold(), QuorumThreshold::TwentyFive);
+        
+        // Later proposals asking for a lower quorum are raised to the new default
+        let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
+        let raised = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
+        assert_eq!(contract.get_proposal(raised).unwrap().governance_params.quorum_threshold, QuorumThreshold::TwentyFive);
+    }
+
+    #[ink::test]
+    fn governance_proposal_rejects_malformed_payload() {
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        contract.register_voter().unwrap();
+        
+        let (title, description, _, governance_params, _) = create_test_proposal_params();
+        let voting_options = VotingOptions {
+            options: vec!["Change".to_string(), "Keep".to_string()],
+            payloads: vec![vec![0xff, 0xff], vec![]],
+            actions: vec![],
+            abstain_index: None,
+        };
+        let result = contract.create_proposal(title, description, ProposalType::Governance, governance_params, voting_options, vec![]);
+        assert_eq!(result.unwrap_err(), crate::errors::Error::InvalidProposal);
+    }
+
+    #[ink::test]
+    fn confirmation_clock_restarts_when_support_drops() {
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        contract.set_confirmation_period(MILLIS_PER_DAY).unwrap();
+        for voter in [accounts.alice, accounts.bob, accounts.charlie] {
+            set_caller(voter);
+            contract.register_voter().unwrap();
+        }
+        
+        set_caller(accounts.alice);
+        let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
+        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
+        assert_eq!(contract.get_decision_state(proposal_id).unwrap(), DecisionState::Deciding);
+        
+        // A clear leader starts the confirmation clock
+        set_block_timestamp::<ink::env::DefaultEnvironment>(100);
+        contract.vote(proposal_id, VoteChoice { option_index: 0, option_text: "Yes".to_string() }).unwrap();
+        assert_eq!(contract.get_decision_state(proposal_id).unwrap(), DecisionState::Confirming { since: 100 });
+        
+        // A tie drops support and stops the clock
+        set_block_timestamp::<ink::env::DefaultEnvironment>(200);
+        set_caller(accounts.bob);
+        contract.vote(proposal_id, VoteChoice { option_index: 1, option_text: "No".to_string() }).unwrap();
+        assert_eq!(contract.get_decision_state(proposal_id).unwrap(), DecisionState::Deciding);
+        
+        // Regaining the lead restarts the clock from scratch
+        set_block_timestamp::<ink::env::DefaultEnvironment>(300);
+        set_caller(accounts.charlie);
+        contract.vote(proposal_id, VoteChoice { option_index: 0, option_text: "Yes".to_string() }).unwrap();
+        assert_eq!(contract.get_decision_state(proposal_id).unwrap(), DecisionState::Confirming { since: 300 });
+        
+        // The first confirmation window would have ended here, but the restarted one has not
+        set_block_timestamp::<ink::env::DefaultEnvironment>(100 + MILLIS_PER_DAY);
+        assert_eq!(contract.update_proposal_status(proposal_id).unwrap_err(), crate::errors::Error::ProposalNotReadyForExecution);
+        
+        // Passes as soon as confirmation completes, before the voting deadline
+        set_block_timestamp::<ink::env::DefaultEnvironment>(300 + MILLIS_PER_DAY);
+        assert_eq!(contract.update_proposal_status(proposal_id).unwrap(), ProposalStatus::Passed);
+        assert!(contract.get_proposal(proposal_id).unwrap().voting_end > 300 + MILLIS_PER_DAY);
+    }
+
+    #[ink::test]
+    fn confirmation_required_before_deadline_or_rejected() {
+        use scale::Encode;
+        
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        contract.set_confirmation_period(MILLIS_PER_DAY).unwrap();
+        contract.register_voter().unwrap();
+        set_caller(accounts.bob);
+        contract.register_voter().unwrap();
+        
+        set_caller(accounts.alice);
+        let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
+        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
+        let voting_end = contract.get_proposal(proposal_id).unwrap().voting_end;
+        
+        contract.vote(proposal_id, VoteChoice { option_index: 0, option_text: "Yes".to_string() }).unwrap();
+        let event = ink::env::test::recorded_events().into_iter().last().unwrap();
+        assert_eq!(event.data, (proposal_id, 0u64).encode());
+        
+        set_caller(accounts.bob);
+        contract.vote(proposal_id, VoteChoice { option_index: 1, option_text: "No".to_string() }).unwrap();
+        let event = ink::env::test::recorded_events().into_iter().last().unwrap();
+        assert_eq!(event.data, proposal_id.encode());
+        
+        // Still deciding when voting ends, so the proposal is rejected
+        set_block_timestamp::<ink::env::DefaultEnvironment>(voting_end + 1);
+        assert_eq!(contract.update_proposal_status(proposal_id).unwrap(), ProposalStatus::Rejected);
+    }
+
+    #[ink::test]
+    fn execute_requires_passed_status_after_refresh() {
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        contract.register_voter().unwrap();
+        set_caller(accounts.bob);
+        contract.register_voter().unwrap();
+        
+        set_caller(accounts.alice);
+        let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
+        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
+        contract.vote(proposal_id, VoteChoice { option_index: 0, option_text: "Yes".to_string() }).unwrap();
+        set_caller(accounts.bob);
+        contract.vote(proposal_id, VoteChoice { option_index: 1, option_text: "No".to_string() }).unwrap();
+        
+        // The tie is refreshed to Rejected, and execution must observe that status
+        let proposal = contract.get_proposal(proposal_id).unwrap();
+        set_block_timestamp::<ink::env::DefaultEnvironment>(proposal.voting_end + 1);
+        assert_eq!(contract.update_proposal_status(proposal_id).unwrap(), ProposalStatus::Rejected);
+        
+        set_block_timestamp::<ink::env::DefaultEnvironment>(proposal.execution_time);
+        assert_eq!(contract.execute_proposal(proposal_id).unwrap_err(), crate::errors::Error::ProposalNotReadyForExecution);
+        let proposal = contract.get_proposal(proposal_id).unwrap();
+        assert_eq!(proposal.status, ProposalStatus::Rejected);
+        assert_eq!(proposal.executed_by, None);
+    }
+
+    #[ink::test]
+    fn contract_accounts_rejected_unless_admin_overrides() {
+        use crate::treasurygovernance::contract_accounts::mark_as_contract;
+        
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        contract.set_allow_contract_voters(false).unwrap();
+        mark_as_contract(accounts.bob);
+        mark_as_contract(accounts.charlie);
+        
+        // A contract cannot register itself
+        set_caller(accounts.bob);
+        assert_eq!(contract.register_voter().unwrap_err(), crate::errors::Error::ContractAccountsNotAllowed);
+        assert!(!contract.is_voter_registered(accounts.bob));
+        
+        // Ordinary accounts are unaffected
+        set_caller(accounts.django);
+        contract.register_voter().unwrap();
+        
+        // The owner can admit a known-good contract such as a multisig
+        set_caller(accounts.alice);
+        assert_eq!(contract.admin_register_voter(accounts.charlie, false).unwrap_err(), crate::errors::Error::ContractAccountsNotAllowed);
+        contract.admin_register_voter(accounts.charlie, true).unwrap();
+        assert!(contract.is_voter_registered(accounts.charlie));
+        
+        // Only the owner may register on someone's behalf
+        set_caller(accounts.django);
+        assert_eq!(contract.admin_register_voter(accounts.bob, true).unwrap_err(), crate::errors::Error::NotAuthorized);
+    }
+
+    #[ink::test]
+    fn winning_option_selects_outcome_action() {
+        use ink::primitives::U256;
+        
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        let contract_account = ink::env::test::callee::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_account_balance(contract_account, U256::from(10_000u128));
+        contract.register_voter().unwrap();
+        
+        let (title, description, proposal_type, governance_params, _) = create_test_proposal_params();
+        let voting_options = VotingOptions {
+            options: vec!["Pay Bob".to_string(), "Do nothing".to_string()],
+            payloads: vec![],
+            actions: vec![OutcomeAction::Transfer { recipient: accounts.bob, amount: 1_000 }, OutcomeAction::NoOp],
+            abstain_index: None,
+        };
+        let paid = contract.create_proposal(title.clone(), description.clone(), proposal_type.clone(), governance_params.clone(), voting_options.clone(), vec![]).unwrap();
+        let skipped = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
+        contract.vote(paid, VoteChoice { option_index: 0, option_text: "Pay Bob".to_string() }).unwrap();
+        contract.vote(skipped, VoteChoice { option_index: 1, option_text: "Do nothing".to_string() }).unwrap();
+        
+        let proposal = contract.get_proposal(paid).unwrap();
+        set_block_timestamp::<ink::env::DefaultEnvironment>(proposal.voting_end + 1);
+        contract.update_proposal_status(paid).unwrap();
+        contract.update_proposal_status(skipped).unwrap();
+        set_block_timestamp::<ink::env::DefaultEnvironment>(proposal.execution_time);
+        
+        let balance_of = |account| ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(account).unwrap();
+        let bob_before = balance_of(accounts.bob);
+        
+        // Option 0 won, so the transfer is paid out
+        contract.execute_proposal(paid).unwrap();
+        assert_eq!(balance_of(accounts.bob), bob_before + U256::from(1_000u128));
+        assert_eq!(balance_of(contract_account), U256::from(9_000u128));
+        
+        // Option 1 won, so execution moves no funds
+        contract.execute_proposal(skipped).unwrap();
+        assert_eq!(balance_of(accounts.bob), bob_before + U256::from(1_000u128));
+        assert_eq!(contract.get_proposal(skipped).unwrap().status, ProposalStatus::Executed);
+    }
+
+    #[ink::test]
+    fn outcome_actions_must_match_options() {
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        contract.register_voter().unwrap();
+        
+        let (title, description, proposal_type, governance_params, mut voting_options) = create_test_proposal_params();
+        voting_options.actions = vec![OutcomeAction::NoOp];
+        let result = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]);
+        assert_eq!(result.unwrap_err(), crate::errors::Error::InvalidProposal);
+    }
+
+    #[ink::test]
+    fn reputation_accrues_at_finalization_and_weights_votes() {
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        contract.set_reputation_config(ReputationConfig { proposer_reward: 10, voter_reward: 5, divisor: 10, max_weight: 3 }).unwrap();
+        for voter in [accounts.alice, accounts.bob] {
+            set_caller(voter);
+            contract.register_voter().unwrap();
+        }
+        
+        // Alice authors two proposals that both voters support
+        let mut now: Timestamp = 0;
+        for _ in 0..2 {
+            set_caller(accounts.alice);
+            let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
+            let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
+            for voter in [accounts.alice, accounts.bob] {
+                set_caller(voter);
+                contract.vote(proposal_id, VoteChoice { option_index: 0, option_text: "Yes".to_string() }).unwrap();
+            }
+            
+            // Nothing is earned at vote time
+            let bob_before = contract.get_reputation(accounts.bob);
+            now = contract.get_proposal(proposal_id).unwrap().voting_end + 1;
+            set_block_timestamp::<ink::env::DefaultEnvironment>(now);
+            assert_eq!(contract.update_proposal_status(proposal_id).unwrap(), ProposalStatus::Passed);
+            assert_eq!(contract.get_reputation(accounts.bob), bob_before + 5);
+        }
+        assert_eq!(contract.get_reputation(accounts.alice), 30);
+        assert_eq!(contract.get_reputation(accounts.bob), 10);
+        
+        // Under reputation weighting, Alice's 1 + 30 / 10 is capped at 3 and Bob gets 1 + 10 / 10
+        set_caller(accounts.alice);
+        contract.set_weight_source(WeightSource::Reputation).unwrap();
+        set_block_timestamp::<ink::env::DefaultEnvironment>(now);
+        let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
+        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
+        contract.vote(proposal_id, VoteChoice { option_index: 0, option_text: "Yes".to_string() }).unwrap();
+        set_caller(accounts.bob);
+        contract.vote(proposal_id, VoteChoice { option_index: 1, option_text: "No".to_string() }).unwrap();
+        
+        assert_eq!(contract.get_proposal(proposal_id).unwrap().vote_counts, vec![3, 2]);
+    }
+
+    #[ink::test]
+    fn voting_window_shorter_than_minimum_rejected() {
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        contract.register_voter().unwrap();
+        assert_eq!(contract.get_min_voting_window(), DEFAULT_MIN_VOTING_WINDOW);
+        
+        // A three day period falls short of a four day minimum window
+        contract.set_min_voting_window(4 * MILLIS_PER_DAY).unwrap();
+        let (title, description, proposal_type, mut governance_params, voting_options) = create_test_proposal_params();
+        governance_params.voting_period = VotingPeriod::ThreeDays;
+        let result = contract.create_proposal(title.clone(), description.clone(), proposal_type.clone(), governance_params.clone(), voting_options.clone(), vec![]);
+        assert_eq!(result.unwrap_err(), crate::errors::Error::InvalidProposal);
+        
+        // Seven days is long enough
+        governance_params.voting_period = VotingPeriod::SevenDays;
+        assert!(contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).is_ok());
+        
+        // Only the owner may change the minimum
+        set_caller(accounts.bob);
+        assert_eq!(contract.set_min_voting_window(0).unwrap_err(), crate::errors::Error::NotAuthorized);
+    }
+
+    #[ink::test]
+    fn ballot_commitment_matches_recorded_votes() {
+        use ink::env::hash::Blake2x256;
+        use scale::Encode;
+        
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        let voters = [accounts.alice, accounts.bob, accounts.charlie];
+        for voter in voters {
+            set_caller(voter);
+            contract.register_voter().unwrap();
+        }
+        
+        set_caller(accounts.alice);
+        let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
+        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
+        for (voter, option_index) in voters.into_iter().zip([0u32, 1, 0]) {
+            set_caller(voter);
+            let option_text = if option_index == 0 { "Yes" } else { "No" };
+            contract.vote(proposal_id, VoteChoice { option_index, option_text: option_text.to_string() }).unwrap();
+        }
+        
+        // Recompute the commitment from the recorded ballots
+        let mut expected = [0u8; 32];
+        for voter in voters {
+            let vote = contract.get_user_vote(proposal_id, voter).unwrap();
+            let mut leaf_input = Vec::new();
+            leaf_input.extend_from_slice(vote.voter.as_bytes());
+            leaf_input.extend_from_slice(&vote.choice.option_index.to_le_bytes());
+            leaf_input.extend_from_slice(&vote.weight.to_le_bytes());
+            let mut leaf = [0u8; 32];
+            ink::env::hash_bytes::<Blake2x256>(&leaf_input, &mut leaf);
+            
+            let chained = [expected, leaf].concat();
+            ink::env::hash_bytes::<Blake2x256>(&chained, &mut expected);
+        }
+        
+        let ballots = contract.get_ballot_commitment(proposal_id).unwrap();
+        assert_eq!(ballots, BallotCommitment { commitment: expected, ballots: 3, sealed: false });
+        
+        // Finalization seals the commitment and publishes it
+        let proposal = contract.get_proposal(proposal_id).unwrap();
+        set_block_timestamp::<ink::env::DefaultEnvironment>(proposal.voting_end + 1);
+        contract.update_proposal_status(proposal_id).unwrap();
+        assert!(contract.get_ballot_commitment(proposal_id).unwrap().sealed);
+        
+        let event = ink::env::test::recorded_events().into_iter().last().unwrap();
+        assert_eq!(event.data, (proposal_id, ProposalStatus::Passed, None::<ink::primitives::H160>, expected).encode());
+    }
+
+    #[ink::test]
+    fn get_proposals_preserves_order_and_marks_missing() {
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        contract.register_voter().unwrap();
+        let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
+        let first = contract.create_proposal(title.clone(), description.clone(), proposal_type.clone(), governance_params.clone(), voting_options.clone(), vec![]).unwrap();
+        let second = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
+        
+        let proposals = contract.get_proposals(vec![second, 99, first]);
+        assert_eq!(proposals.len(), 3);
+        assert_eq!(proposals[0].as_ref().unwrap().id, second);
+        assert!(proposals[1].is_none());
+        assert_eq!(proposals[2].as_ref().unwrap().id, first);
+        
+        // Requests beyond the page size are truncated
+        let many = vec![first; MAX_PAGE_SIZE as usize + 10];
+        assert_eq!(contract.get_proposals(many).len(), MAX_PAGE_SIZE as usize);
+    }
+
+    #[ink::test]
+    fn list_proposals_pages_through_ids() {
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        contract.register_voter().unwrap();
+        let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
+        let ids: Vec<u32> = (0..3)
+            .map(|_| contract.create_proposal(title.clone(), description.clone(), proposal_type.clone(), governance_params.clone(), voting_options.clone(), vec![]).unwrap())
+            .collect();
+        
+        let ids_of = |page: Vec<Proposal>| page.into_iter().map(|proposal| proposal.id).collect::<Vec<u32>>();
+        assert_eq!(ids_of(contract.list_proposals(0, 2)), vec![ids[0], ids[1]]);
+        assert_eq!(ids_of(contract.list_proposals(ids[1], 10)), vec![ids[1], ids[2]]);
+        assert!(contract.list_proposals(ids[2] + 1, 10).is_empty());
+        assert_eq!(contract.list_proposals(0, u32::MAX).len(), 3);
+    }
+
+    #[ink::test]
+    fn merkle_eligibility_pinned_per_proposal() {
+        use ink::env::hash::Blake2x256;
+        use ink::primitives::H160;
+        
+        fn leaf(account: H160) -> [u8; 32] {
+            let mut output = [0u8; 32];
+            ink::env::hash_bytes::<Blake2x256>(account.as_bytes(), &mut output);
+            output
+        }
+        fn node(a: [u8; 32], b: [u8; 32]) -> [u8; 32] {
+            let pair = if a <= b { [a, b].concat() } else { [b, a].concat() };
+            let mut output = [0u8; 32];
+            ink::env::hash_bytes::<Blake2x256>(&pair, &mut output);
+            output
+        }
+        
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        let mut contract = TreasuryGovernance::new();
+        
+        // Tree over Alice, Bob, Charlie and Django
+        let (alice, bob, charlie, django) = (leaf(accounts.alice), leaf(accounts.bob), leaf(accounts.charlie), leaf(accounts.django));
+        let (left, right) = (node(alice, bob), node(charlie, django));
+        let first_root = EligibilityRoot { root: node(left, right), eligible_count: 4 };
+        contract.set_eligibility_root(Some(first_root.clone())).unwrap();
+        
+        let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
+        let older = contract.create_proposal(title.clone(), description.clone(), proposal_type.clone(), governance_params.clone(), voting_options.clone(), vec![]).unwrap();
+        assert_eq!(contract.get_proposal_eligibility(older), Some(first_root));
+        
+        let yes = VoteChoice { option_index: 0, option_text: "Yes".to_string() };
+        contract.vote_with_proof(older, yes.clone(), vec![bob, right]).unwrap();
+        assert_eq!(contract.vote_with_proof(older, yes.clone(), vec![bob, right]).unwrap_err(), crate::errors::Error::AlreadyVoted);
+        
+        // Someone else's proof does not verify for the caller
+        set_caller(accounts.eve);
+        assert_eq!(contract.vote_with_proof(older, yes.clone(), vec![bob, right]).unwrap_err(), crate::errors::Error::InvalidProof);
+        
+        // Registration alone is not enough on a pinned proposal
+        contract.register_voter().unwrap();
+        assert_eq!(contract.vote(older, yes.clone()).unwrap_err(), crate::errors::Error::NotAuthorized);
+        
+        // Rotate to a tree over Charlie and Django only
+        set_caller(accounts.alice);
+        contract.set_eligibility_root(Some(EligibilityRoot { root: right, eligible_count: 2 })).unwrap();
+        let newer = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
+        
+        // Bob stays eligible on the older proposal but not on the newer one
+        set_caller(accounts.bob);
+        contract.vote_with_proof(older, yes.clone(), vec![alice, right]).unwrap();
+        assert_eq!(contract.vote_with_proof(newer, yes.clone(), vec![alice, right]).unwrap_err(), crate::errors::Error::InvalidProof);
+        
+        set_caller(accounts.charlie);
+        contract.vote_with_proof(newer, yes, vec![django]).unwrap();
+        assert_eq!(contract.get_proposal(older).unwrap().total_voters, 2);
+        assert_eq!(contract.get_proposal(newer).unwrap().total_voters, 1);
+    }
+
+    #[ink::test]
+    fn vote_rewards_stop_when_budget_exhausted() {
+        use ink::primitives::U256;
+        
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        let contract_account = ink::env::test::callee::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_account_balance(contract_account, U256::from(1_000u128));
+        
+        // Budget covers two rewards of 100
+        contract.set_vote_reward(100, 250).unwrap();
+        for voter in [accounts.alice, accounts.bob, accounts.charlie] {
+            set_caller(voter);
+            contract.register_voter().unwrap();
+        }
+        
+        set_caller(accounts.alice);
+        let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
+        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
+        
+        let balance_of = |account| ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(account).unwrap();
+        let before: Vec<U256> = [accounts.alice, accounts.bob, accounts.charlie].iter().map(|&account| balance_of(account)).collect();
+        
+        for voter in [accounts.alice, accounts.bob, accounts.charlie] {
+            set_caller(voter);
+            contract.vote(proposal_id, VoteChoice { option_index: 0, option_text: "Yes".to_string() }).unwrap();
+        }
+        
+        // The first two voters are rewarded, the third vote still counts but is unpaid
+        assert_eq!(balance_of(accounts.alice), before[0] + U256::from(100u128));
+        assert_eq!(balance_of(accounts.bob), before[1] + U256::from(100u128));
+        assert_eq!(balance_of(accounts.charlie), before[2]);
+        assert_eq!(contract.get_rewards_distributed(proposal_id), 200);
+        assert_eq!(contract.get_proposal(proposal_id).unwrap().total_voters, 3);
+        assert_eq!(balance_of(contract_account), U256::from(800u128));
+    }
+
+    #[ink::test]
+    fn designated_voters_restrict_voting_and_quorum() {
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        let extra_voters = [ink::primitives::H160::from([0x11; 20]), ink::primitives::H160::from([0x12; 20])];
+        for voter in [accounts.alice, accounts.bob, accounts.charlie, accounts.django, accounts.eve, accounts.frank].into_iter().chain(extra_voters) {
+            set_caller(voter);
+            contract.register_voter().unwrap();
+        }
+        
+        // A committee of Alice and Bob, with quorum measured against the two of them
+        set_caller(accounts.alice);
+        let (title, description, proposal_type, mut governance_params, voting_options) = create_test_proposal_params();
+        governance_params.quorum_threshold = QuorumThreshold::TwentyFive;
+        governance_params.eligible_voters = Some(vec![accounts.alice, accounts.bob]);
+        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
+        
+        set_caller(accounts.bob);
+        contract.vote(proposal_id, VoteChoice { option_index: 0, option_text: "Yes".to_string() }).unwrap();
+        
+        // Charlie is registered but not on the committee
+        set_caller(accounts.charlie);
+        assert_eq!(contract.vote(proposal_id, VoteChoice { option_index: 0, option_text: "Yes".to_string() }).unwrap_err(), crate::errors::Error::NotAuthorized);
+        
+        // 25% of eight registered voters would need two votes, but of the two members only one
+        let proposal = contract.get_proposal(proposal_id).unwrap();
+        set_block_timestamp::<ink::env::DefaultEnvironment>(proposal.voting_end + 1);
+        assert_eq!(contract.update_proposal_status(proposal_id).unwrap(), ProposalStatus::Passed);
+    }
+
+    #[ink::test]
+    fn designated_voters_must_be_valid() {
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        contract.register_voter().unwrap();
+        
+        let (title, description, proposal_type, mut governance_params, voting_options) = create_test_proposal_params();
+        for eligible_voters in [vec![], vec![accounts.bob, accounts.bob]] {
+            governance_params.eligible_voters = Some(eligible_voters);
+            let result = contract.create_proposal(title.clone(), description.clone(), proposal_type.clone(), governance_params.clone(), voting_options.clone(), vec![]);
+            assert_eq!(result.unwrap_err(), crate::errors::Error::InvalidProposal);
+        }
+    }
+
+    #[ink::test]
+    fn three_option_actions_execute_only_the_winner() {
+        use ink::primitives::U256;
+        
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        let contract_account = ink::env::test::callee::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_account_balance(contract_account, U256::from(100_000u128));
+        for voter in [accounts.alice, accounts.bob] {
+            set_caller(voter);
+            contract.register_voter().unwrap();
+        }
+        
+        let (title, description, proposal_type, governance_params, _) = create_test_proposal_params();
+        let voting_options = VotingOptions {
+            options: vec!["Fund Bob".to_string(), "Fund Charlie".to_string(), "Do nothing".to_string()],
+            payloads: vec![],
+            actions: vec![
+                OutcomeAction::Transfer { recipient: accounts.bob, amount: 5_000 },
+                OutcomeAction::Transfer { recipient: accounts.charlie, amount: 5_000 },
+                OutcomeAction::NoOp,
+            ],
+            abstain_index: None,
+        };
+        
+        // One proposal per outcome, plus one where Alice and Bob split and tie
+        set_caller(accounts.alice);
+        let mut proposal_ids = Vec::new();
+        for _ in 0..4 {
+            proposal_ids.push(contract.create_proposal(title.clone(), description.clone(), proposal_type.clone(), governance_params.clone(), voting_options.clone(), vec![]).unwrap());
+        }
+        for (&proposal_id, winner) in proposal_ids.iter().zip(0u32..3) {
+            contract.vote(proposal_id, VoteChoice { option_index: winner, option_text: voting_options.options[winner as usize].clone() }).unwrap();
+        }
+        contract.vote(proposal_ids[3], VoteChoice { option_index: 0, option_text: "Fund Bob".to_string() }).unwrap();
+        set_caller(accounts.bob);
+        contract.vote(proposal_ids[3], VoteChoice { option_index: 1, option_text: "Fund Charlie".to_string() }).unwrap();
+        
+        let results = contract.get_results_with_actions(proposal_ids[0]).unwrap();
+        assert_eq!(results.iter().map(|(_, _, has_action)| *has_action).collect::<Vec<_>>(), vec![true, true, false]);
+        assert_eq!(contract.get_option_action(proposal_ids[0], 2).unwrap(), None);
+        
+        let proposal = contract.get_proposal(proposal_ids[0]).unwrap();
+        set_block_timestamp::<ink::env::DefaultEnvironment>(proposal.voting_end + 1);
+        for &proposal_id in &proposal_ids {
+            contract.update_proposal_status(proposal_id).unwrap();
+        }
+        set_block_timestamp::<ink::env::DefaultEnvironment>(proposal.execution_time);
+        
+        let balance_of = |account| ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(account).unwrap();
+        let (bob_before, charlie_before) = (balance_of(accounts.bob), balance_of(accounts.charlie));
+        
+        contract.execute_proposal(proposal_ids[0]).unwrap();
+        assert_eq!(balance_of(accounts.bob), bob_before + U256::from(5_000u128));
+        assert_eq!(balance_of(accounts.charlie), charlie_before);
+        
+        contract.execute_proposal(proposal_ids[1]).unwrap();
+        assert_eq!(balance_of(accounts.charlie), charlie_before + U256::from(5_000u128));
+        
+        contract.execute_proposal(proposal_ids[2]).unwrap();
+        assert_eq!(balance_of(contract_account), U256::from(90_000u128));
+        
+        // A tie rejects the proposal, so neither funded option runs
+        assert_eq!(contract.execute_proposal(proposal_ids[3]).unwrap_err(), crate::errors::Error::ProposalNotReadyForExecution);
+        assert_eq!(balance_of(contract_account), U256::from(90_000u128));
+    }
+
+    #[ink::test]
+    fn option_actions_validated_per_variant() {
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        contract.register_voter().unwrap();
+        
+        let (title, description, proposal_type, governance_params, mut voting_options) = create_test_proposal_params();
+        let invalid_actions = [
+            OutcomeAction::Transfer { recipient: accounts.bob, amount: 0 },
+            OutcomeAction::Transfer { recipient: ink::primitives::H160::zero(), amount: 10 },
+            OutcomeAction::CallContract { callee: accounts.bob, selector: [0; 4], input: vec![0; MAX_OPTION_PAYLOAD_SIZE + 1] },
+        ];
+        for action in invalid_actions {
+            voting_options.actions = vec![action, OutcomeAction::NoOp];
+            let result = contract.create_proposal(title.clone(), description.clone(), proposal_type.clone(), governance_params.clone(), voting_options.clone(), vec![]);
+            assert_eq!(result.unwrap_err(), crate::errors::Error::InvalidProposal);
+        }
+    }
+
+    #[ink::test]
+    fn vote_timeline_buckets_votes_by_time() {
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        for voter in [accounts.alice, accounts.bob, accounts.charlie, accounts.django] {
+            set_caller(voter);
+            contract.register_voter().unwrap();
+        }
+        
+        set_caller(accounts.alice);
+        let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
+        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
+        
+        // Two votes in the first hour, none in the second, one each in the third and fourth
+        for (voter, timestamp) in [(accounts.alice, 100u64), (accounts.bob, 3_599_999), (accounts.charlie, 7_200_000), (accounts.django, 14_000_000)] {
+            set_block_timestamp::<ink::env::DefaultEnvironment>(timestamp);
+            set_caller(voter);
+            contract.vote(proposal_id, VoteChoice { option_index: 0, option_text: "Yes".to_string() }).unwrap();
+        }
+        
+        assert_eq!(contract.get_vote_timeline(proposal_id, 3_600_000).unwrap(), vec![(0, 2), (7_200_000, 1), (10_800_000, 1)]);
+        assert_eq!(contract.get_vote_timeline(proposal_id, MILLIS_PER_DAY).unwrap(), vec![(0, 4)]);
+        assert_eq!(contract.get_vote_timeline(proposal_id, 0).unwrap_err(), crate::errors::Error::InvalidProposal);
+    }
+
+    #[ink::test]
+    fn simulated_finalization_matches_actual_outcome() {
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        let voters = [accounts.alice, accounts.bob, accounts.charlie, accounts.django, accounts.eve, accounts.frank, ink::primitives::H160::from([0x11; 20]), ink::primitives::H160::from([0x12; 20])];
+        for voter in voters {
+            set_caller(voter);
+            contract.register_voter().unwrap();
+        }
+        
+        // Each scenario lists the quorum and the options chosen by the first voters
+        let scenarios = [
+            (QuorumThreshold::TwentyFive, vec![0u32, 0], ProposalStatus::Passed, None),
+            (QuorumThreshold::TwentyFive, vec![0], ProposalStatus::Rejected, Some(FinalizationCheck::Quorum)),
+            (QuorumThreshold::TwentyFive, vec![0, 1], ProposalStatus::Rejected, Some(FinalizationCheck::Tie)),
+            (QuorumThreshold::Ten, vec![], ProposalStatus::Rejected, Some(FinalizationCheck::Quorum)),
+        ];
+        
+        let mut proposal_ids = Vec::new();
+        for (quorum_threshold, choices, _, _) in scenarios.iter() {
+            set_caller(accounts.alice);
+            let (title, description, proposal_type, mut governance_params, voting_options) = create_test_proposal_params();
+            governance_params.quorum_threshold = quorum_threshold.clone();
+            let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
+            for (&voter, &option_index) in voters.iter().zip(choices.iter()) {
+                set_caller(voter);
+                let option_text = if option_index == 0 { "Yes" } else { "No" };
+                contract.vote(proposal_id, VoteChoice { option_index, option_text: option_text.to_string() }).unwrap();
+            }
+            proposal_ids.push(proposal_id);
+        }
+        
+        let voting_end = contract.get_proposal(proposal_ids[0]).unwrap().voting_end;
+        set_block_timestamp::<ink::env::DefaultEnvironment>(voting_end + 1);
+        for (&proposal_id, (_, _, expected_status, expected_check)) in proposal_ids.iter().zip(scenarios.iter()) {
+            let simulated = contract.simulate_finalization(proposal_id).unwrap();
+            assert_eq!(simulated.failing_check, *expected_check);
+            assert_eq!(simulated.status, *expected_status);
+            
+            // Simulation changes nothing, and finalization agrees with it
+            assert_eq!(contract.get_proposal(proposal_id).unwrap().status, ProposalStatus::Active);
+            assert_eq!(contract.update_proposal_status(proposal_id).unwrap(), simulated.status);
+        }
+        assert_eq!(contract.simulate_finalization(proposal_ids[0]).unwrap_err(), crate::errors::Error::ProposalNotActive);
+    }
+
+    #[ink::test]
+    fn simulated_finalization_reports_running_confirmation() {
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        contract.set_confirmation_period(MILLIS_PER_DAY).unwrap();
+        contract.register_voter().unwrap();
+        let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
+        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
+        contract.vote(proposal_id, VoteChoice { option_index: 0, option_text: "Yes".to_string() }).unwrap();
+        
+        let simulated = contract.simulate_finalization(proposal_id).unwrap();
+        assert_eq!(simulated, SimulatedOutcome { status: ProposalStatus::Active, winning_option: Some(0), failing_check: Some(FinalizationCheck::Confirmation) });
+        
+        set_block_timestamp::<ink::env::DefaultEnvironment>(MILLIS_PER_DAY);
+        assert_eq!(contract.simulate_finalization(proposal_id).unwrap().status, ProposalStatus::Passed);
+        assert_eq!(contract.update_proposal_status(proposal_id).unwrap(), ProposalStatus::Passed);
+    }
+
+    #[ink::test]
+    fn absent_voters_lose_weight_until_they_participate() {
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        contract.set_tenure_weight_per_day(10).unwrap();
+        contract.set_absence_slashing(Some(AbsenceSlashing { miss_threshold: 2, retained_percent: 50 })).unwrap();
+        for voter in [accounts.alice, accounts.bob] {
+            set_caller(voter);
+            contract.register_voter().unwrap();
+        }
+        
+        // Only Alice votes on the first two proposals
+        set_caller(accounts.alice);
+        let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
+        let mut missed = Vec::new();
+        for _ in 0..2 {
+            let proposal_id = contract.create_proposal(title.clone(), description.clone(), proposal_type.clone(), governance_params.clone(), voting_options.clone(), vec![]).unwrap();
+            contract.vote(proposal_id, VoteChoice { option_index: 0, option_text: "Yes".to_string() }).unwrap();
+            missed.push(proposal_id);
+        }
+        let voting_end = contract.get_proposal(missed[0]).unwrap().voting_end;
+        set_block_timestamp::<ink::env::DefaultEnvironment>(voting_end + 1);
+        for proposal_id in missed {
+            contract.update_proposal_status(proposal_id).unwrap();
+        }
+        assert_eq!(contract.get_missed_proposals(accounts.alice), 0);
+        assert_eq!(contract.get_missed_proposals(accounts.bob), 2);
+        
+        // Ten days in, both earned 1 + 10 * 10 but Bob keeps only half
+        let now = 10 * MILLIS_PER_DAY;
+        set_block_timestamp::<ink::env::DefaultEnvironment>(now);
+        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
+        contract.vote(proposal_id, VoteChoice { option_index: 0, option_text: "Yes".to_string() }).unwrap();
+        set_caller(accounts.bob);
+        contract.vote(proposal_id, VoteChoice { option_index: 1, option_text: "No".to_string() }).unwrap();
+        assert_eq!(contract.get_proposal(proposal_id).unwrap().vote_counts, vec![101, 50]);
+        
+        // Participating in a finalized proposal clears the streak
+        let proposal = contract.get_proposal(proposal_id).unwrap();
+        set_block_timestamp::<ink::env::DefaultEnvironment>(proposal.voting_end + 1);
+        contract.update_proposal_status(proposal_id).unwrap();
+        assert_eq!(contract.get_missed_proposals(accounts.bob), 0);
+    }
+
+    #[ink::test]
+    fn tally_audit_detects_and_repairs_mismatch() {
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        for voter in [accounts.alice, accounts.bob, accounts.charlie] {
+            set_caller(voter);
+            contract.register_voter().unwrap();
+        }
+        
+        set_caller(accounts.alice);
+        let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
+        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
+        for (voter, option_index) in [(accounts.alice, 0u32), (accounts.bob, 1), (accounts.charlie, 0)] {
+            set_caller(voter);
+            let option_text = if option_index == 0 { "Yes" } else { "No" };
+            contract.vote(proposal_id, VoteChoice { option_index, option_text: option_text.to_string() }).unwrap();
+        }
+        contract.corrupt_tally_for_test(proposal_id, vec![1, 5]);
+        
+        // Repair needs a complete audit first
+        set_caller(accounts.alice);
+        assert_eq!(contract.repair_tally(proposal_id).unwrap_err(), crate::errors::Error::InvalidProposal);
+        
+        // Audit in two chunks; skipping ahead is refused
+        let first = contract.audit_tally(proposal_id, 0, 2).unwrap();
+        assert_eq!((first.next_offset, first.complete, first.consistent), (2, false, None));
+        assert_eq!(contract.audit_tally(proposal_id, 3, 2).unwrap_err(), crate::errors::Error::InvalidProposal);
+        let second = contract.audit_tally(proposal_id, 2, 2).unwrap();
+        assert_eq!((second.complete, second.consistent), (true, Some(false)));
+        assert_eq!(second.recomputed, vec![2, 1]);
+        
+        // Only the guardian may repair
+        set_caller(accounts.bob);
+        assert_eq!(contract.repair_tally(proposal_id).unwrap_err(), crate::errors::Error::NotAuthorized);
+        set_caller(accounts.alice);
+        contract.repair_tally(proposal_id).unwrap();
+        assert_eq!(contract.get_proposal(proposal_id).unwrap().vote_counts, vec![2, 1]);
+        
+        // A fresh audit now agrees with the stored counts
+        let report = contract.audit_tally(proposal_id, 0, 10).unwrap();
+        assert_eq!(report.consistent, Some(true));
+    }
+
+    #[ink::test]
+    fn simulated_execution_matches_real_execution() {
+        use ink::primitives::U256;
+        
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        let contract_account = ink::env::test::callee::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_account_balance(contract_account, U256::from(10_000u128));
+        contract.register_voter().unwrap();
+        
+        let (title, description, proposal_type, governance_params, mut voting_options) = create_test_proposal_params();
+        voting_options.actions = vec![OutcomeAction::Transfer { recipient: accounts.bob, amount: 1_500 }, OutcomeAction::NoOp];
+        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
+        contract.vote(proposal_id, VoteChoice { option_index: 0, option_text: "Yes".to_string() }).unwrap();
+        
+        let proposal = contract.get_proposal(proposal_id).unwrap();
+        set_block_timestamp::<ink::env::DefaultEnvironment>(proposal.voting_end + 1);
+        contract.update_proposal_status(proposal_id).unwrap();
+        
+        // Before the execution time both refuse alike
+        assert_eq!(contract.simulate_execution(proposal_id).unwrap_err(), crate::errors::Error::ProposalNotReadyForExecution);
+        
+        set_block_timestamp::<ink::env::DefaultEnvironment>(proposal.execution_time);
+        let (projected_balance, projected_status) = contract.simulate_execution(proposal_id).unwrap();
+        assert_eq!(projected_balance, 8_500);
+        
+        // Simulation moved nothing
+        let balance_of = |account| ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(account).unwrap();
+        assert_eq!(balance_of(contract_account), U256::from(10_000u128));
+        assert_eq!(contract.get_proposal(proposal_id).unwrap().status, ProposalStatus::Passed);
+        
+        contract.execute_proposal(proposal_id).unwrap();
+        assert_eq!(balance_of(contract_account), U256::from(projected_balance));
+        assert_eq!(contract.get_proposal(proposal_id).unwrap().status, projected_status);
+    }
+
+    #[ink::test]
+    fn revisions_form_a_linked_chain() {
+        use scale::Encode;
+        
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        contract.register_voter().unwrap();
+        set_caller(accounts.bob);
+        contract.register_voter().unwrap();
+        
+        set_caller(accounts.alice);
+        let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
+        let original = contract.create_proposal(title, description, proposal_type, governance_params, voting_options.clone(), vec![]).unwrap();
+        
+        // Only the proposer may revise
+        set_caller(accounts.bob);
+        let result = contract.revise_proposal(original, "Stolen".to_string(), "Not mine".to_string(), voting_options.clone());
+        assert_eq!(result.unwrap_err(), crate::errors::Error::NotAuthorized);
+        
+        set_caller(accounts.alice);
+        let first = contract.revise_proposal(original, "Revision 1".to_string(), "Clarified scope".to_string(), voting_options.clone()).unwrap();
+        
+        // Retiring the original is announced as a cancellation, followed by the revision
+        let events: Vec<_> = ink::env::test::recorded_events().collect();
+        let [.., cancelled, revised] = events.as_slice() else { panic!("expected events") };
+        assert_eq!(cancelled.data, (original, accounts.alice).encode());
+        assert_eq!(revised.data, (original, first).encode());
+        
+        let second = contract.revise_proposal(first, "Revision 2".to_string(), "Final wording".to_string(), voting_options.clone()).unwrap();
+        
+        assert_eq!(contract.get_proposal(original).unwrap().status, ProposalStatus::Cancelled);
+        assert_eq!(contract.get_proposal(first).unwrap().status, ProposalStatus::Cancelled);
+        assert_eq!(contract.get_proposal(second).unwrap().revision_of, Some(first));
+        
+        // The chain is the same from any version
+        for proposal_id in [original, first, second] {
+            assert_eq!(contract.get_revision_chain(proposal_id), vec![original, first, second]);
+        }
+        assert_eq!(contract.get_dashboard().status_counts.cancelled, 2);
+        
+        // Once voting starts the proposal can no longer be revised
+        contract.vote(second, VoteChoice { option_index: 0, option_text: "Yes".to_string() }).unwrap();
+        let result = contract.revise_proposal(second, "Revision 3".to_string(), "Too late".to_string(), voting_options);
+        assert_eq!(result.unwrap_err(), crate::errors::Error::AlreadyVoted);
+    }
+
+    #[ink::test]
+    fn governance_halt_blocks_creation_until_expiry_or_resume() {
+        use scale::Encode;
+        
+        fn governance_proposal(contract: &mut TreasuryGovernance, action: GovernanceAction) -> u32 {
+            let (title, description, _, governance_params, _) = create_test_proposal_params();
+            let voting_options = VotingOptions {
+                options: vec!["Apply".to_string(), "Reject".to_string()],
+                payloads: vec![ParameterChange { action, sunset_after: None }.encode(), vec![]],
+                actions: vec![],
+                abstain_index: None,
+            };
+            let proposal_id = contract.create_proposal(title, description, ProposalType::Governance, governance_params, voting_options, vec![]).unwrap();
+            contract.vote(proposal_id, VoteChoice { option_index: 0, option_text: "Apply".to_string() }).unwrap();
+            proposal_id
+        }
+        
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        contract.register_voter().unwrap();
+        
+        let halt_until = 20 * MILLIS_PER_DAY;
+        let timed_halt = governance_proposal(&mut contract, GovernanceAction::Halt { until: Some(halt_until) });
+        let open_halt = governance_proposal(&mut contract, GovernanceAction::Halt { until: None });
+        let resume = governance_proposal(&mut contract, GovernanceAction::Resume);
+        let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
+        let in_flight = contract.create_proposal(title.clone(), description.clone(), proposal_type.clone(), governance_params.clone(), voting_options.clone(), vec![]).unwrap();
+        contract.vote(in_flight, VoteChoice { option_index: 0, option_text: "Yes".to_string() }).unwrap();
+        
+        let proposal = contract.get_proposal(timed_halt).unwrap();
+        set_block_timestamp::<ink::env::DefaultEnvironment>(proposal.voting_end + 1);
+        for proposal_id in [timed_halt, open_halt, resume, in_flight] {
+            contract.update_proposal_status(proposal_id).unwrap();
+        }
+        set_block_timestamp::<ink::env::DefaultEnvironment>(proposal.execution_time);
+        contract.execute_proposal(timed_halt).unwrap();
+        assert_eq!(contract.get_governance_halt(), Some(GovernanceHalt { until: Some(halt_until) }));
+        
+        // Creation is blocked, but existing proposals still execute
+        let result = contract.create_proposal(title.clone(), description.clone(), proposal_type.clone(), governance_params.clone(), voting_options.clone(), vec![]);
+        assert_eq!(result.unwrap_err(), crate::errors::Error::GovernanceHalted);
+        contract.execute_proposal(in_flight).unwrap();
+        
+        // The timed halt lapses on its own
+        set_block_timestamp::<ink::env::DefaultEnvironment>(halt_until);
+        assert_eq!(contract.get_governance_halt(), None);
+        assert!(contract.create_proposal(title.clone(), description.clone(), proposal_type.clone(), governance_params.clone(), voting_options.clone(), vec![]).is_ok());
+        
+        // An open-ended halt lasts until a resume proposal executes
+        contract.execute_proposal(open_halt).unwrap();
+        let result = contract.create_proposal(title.clone(), description.clone(), proposal_type.clone(), governance_params.clone(), voting_options.clone(), vec![]);
+        assert_eq!(result.unwrap_err(), crate::errors::Error::GovernanceHalted);
+        contract.execute_proposal(resume).unwrap();
+        assert_eq!(contract.get_governance_halt(), None);
+        assert!(contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).is_ok());
+    }
+
+    #[ink::test]
+    fn proposing_requires_minimum_weight() {
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        contract.set_tenure_weight_per_day(10).unwrap();
+        contract.set_min_weight_to_propose(20).unwrap();
+        contract.register_voter().unwrap();
+        let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
+        
+        // After one day Alice holds 1 + 10, short of 20
+        set_block_timestamp::<ink::env::DefaultEnvironment>(MILLIS_PER_DAY);
+        let result = contract.create_proposal(title.clone(), description.clone(), proposal_type.clone(), governance_params.clone(), voting_options.clone(), vec![]);
+        assert_eq!(result.unwrap_err(), crate::errors::Error::InsufficientVotingPower);
+        
+        // After two days she holds 21
+        set_block_timestamp::<ink::env::DefaultEnvironment>(2 * MILLIS_PER_DAY);
+        assert!(contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).is_ok());
+    }
+
+    fn enact_parameter_change(contract: &mut TreasuryGovernance, change: ParameterChange) -> Timestamp {
+        use scale::Encode;
+        
+        let (title, description, _, governance_params, _) = create_test_proposal_params();
+        let voting_options = VotingOptions {
+            options: vec!["Apply".to_string(), "Reject".to_string()],
+            payloads: vec![change.encode(), vec![]],
+            actions: vec![],
+            abstain_index: None,
+        };
+        let proposal_id = contract.create_proposal(title, description, ProposalType::Governance, governance_params, voting_options, vec![]).unwrap();
+        contract.vote(proposal_id, VoteChoice { option_index: 0, option_text: "Apply".to_string() }).unwrap();
+        
+        let proposal = contract.get_proposal(proposal_id).unwrap();
+        set_block_timestamp::<ink::env::DefaultEnvironment>(proposal.voting_end + 1);
+        assert_eq!(contract.update_proposal_status(proposal_id).unwrap(), ProposalStatus::Passed);
+        set_block_timestamp::<ink::env::DefaultEnvironment>(proposal.execution_time);
+        contract.execute_proposal(proposal_id).unwrap();
+        proposal.execution_time
+    }
+
+    #[ink::test]
+    fn sunset_restores_previous_parameter_value() {
+        use scale::Encode;
+        
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        contract.register_voter().unwrap();
+        
+        let executed_at = enact_parameter_change(&mut contract, ParameterChange {
+            action: GovernanceAction::SetDefaultQuorum(QuorumThreshold::TwentyFive),
+            sunset_after: Some(2 * MILLIS_PER_DAY),
+        });
+        assert_eq!(contract.get_default_quorum_threshold(), QuorumThreshold::TwentyFive);
+        assert_eq!(contract.get_pending_sunsets(), vec![PendingSunset {
+            revert: GovernanceAction::SetDefaultQuorum(QuorumThreshold::Ten),
+            revert_at: executed_at + 2 * MILLIS_PER_DAY,
+        }]);
+        
+        // Nothing reverts before the sunset passes
+        assert_eq!(contract.apply_sunsets(), 0);
+        assert_eq!(contract.get_default_quorum_threshold(), QuorumThreshold::TwentyFive);
+        
+        set_block_timestamp::<ink::env::DefaultEnvironment>(executed_at + 2 * MILLIS_PER_DAY);
+        assert_eq!(contract.apply_sunsets(), 1);
+        assert_eq!(contract.get_default_quorum_threshold(), QuorumThreshold::Ten);
+        assert!(contract.get_pending_sunsets().is_empty());
+        
+        let event = ink::env::test::recorded_events().into_iter().last().unwrap();
+        assert_eq!(event.data, GovernanceAction::SetDefaultQuorum(QuorumThreshold::Ten).encode());
+    }
+
+    #[ink::test]
+    fn renewal_before_sunset_cancels_pending_revert() {
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        contract.register_voter().unwrap();
+        
+        let executed_at = enact_parameter_change(&mut contract, ParameterChange {
+            action: GovernanceAction::SetDefaultQuorum(QuorumThreshold::TwentyFive),
+            sunset_after: Some(30 * MILLIS_PER_DAY),
+        });
+        enact_parameter_change(&mut contract, ParameterChange {
+            action: GovernanceAction::SetDefaultQuorum(QuorumThreshold::TwentyFive),
+            sunset_after: None,
+        });
+        assert!(contract.get_pending_sunsets().is_empty());
+        
+        set_block_timestamp::<ink::env::DefaultEnvironment>(executed_at + 30 * MILLIS_PER_DAY);
+        assert_eq!(contract.apply_sunsets(), 0);
+        assert_eq!(contract.get_default_quorum_threshold(), QuorumThreshold::TwentyFive);
+    }
+
+    #[ink::test]
+    fn sunsets_for_different_parameters_are_tracked_independently() {
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        contract.register_voter().unwrap();
+        
+        let quorum_executed_at = enact_parameter_change(&mut contract, ParameterChange {
+            action: GovernanceAction::SetDefaultQuorum(QuorumThreshold::TwentyFive),
+            sunset_after: Some(30 * MILLIS_PER_DAY),
+        });
+        let cap_executed_at = enact_parameter_change(&mut contract, ParameterChange {
+            action: GovernanceAction::SetMaxActiveProposals(3),
+            sunset_after: Some(3 * MILLIS_PER_DAY),
+        });
+        assert_eq!(contract.get_pending_sunsets().len(), 2);
+        
+        // The proposal cap sunsets first while the quorum change stays in force
+        set_block_timestamp::<ink::env::DefaultEnvironment>(cap_executed_at + 3 * MILLIS_PER_DAY);
+        assert_eq!(contract.apply_sunsets(), 1);
+        assert_eq!(contract.get_max_active_proposals(), 0);
+        assert_eq!(contract.get_default_quorum_threshold(), QuorumThreshold::TwentyFive);
+        
+        set_block_timestamp::<ink::env::DefaultEnvironment>(quorum_executed_at + 30 * MILLIS_PER_DAY);
+        assert_eq!(contract.apply_sunsets(), 1);
+        assert_eq!(contract.get_default_quorum_threshold(), QuorumThreshold::Ten);
+        assert!(contract.get_pending_sunsets().is_empty());
+    }
+
+    #[ink::test]
+    fn get_limits_reflects_updated_settings() {
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        let defaults = contract.get_limits();
+        assert_eq!(defaults.max_active_proposals, 0);
+        assert_eq!(defaults.min_voting_window, DEFAULT_MIN_VOTING_WINDOW);
+        
+        contract.set_max_active_proposals(4).unwrap();
+        contract.set_max_voters(50).unwrap();
+        contract.set_min_distinct_participants(3).unwrap();
+        contract.set_tenure_weight_per_day(2).unwrap();
+        contract.set_voting_probation(MILLIS_PER_DAY, false).unwrap();
+        contract.set_max_reasonable_timestamp(1_000_000).unwrap();
+        contract.set_confirmation_period(2 * MILLIS_PER_DAY).unwrap();
+        contract.set_min_voting_window(7_200_000).unwrap();
+        contract.set_min_weight_to_propose(5).unwrap();
+        contract.set_max_effective_power(30).unwrap();
+        contract.set_vote_reward(10, 100).unwrap();
+        contract.set_reputation_config(ReputationConfig { max_weight: 20, ..ReputationConfig::default() }).unwrap();
+        
+        assert_eq!(contract.get_limits(), Limits {
+            max_active_proposals: 4,
+            max_voters: 50,
+            min_distinct_participants: 3,
+            tenure_weight_per_day: 2,
+            voting_probation: MILLIS_PER_DAY,
+            max_reasonable_timestamp: 1_000_000,
+            confirmation_period: 2 * MILLIS_PER_DAY,
+            min_voting_window: 7_200_000,
+            min_weight_to_propose: 5,
+            max_effective_power: 30,
+            vote_reward: 10,
+            vote_reward_budget: 100,
+            max_reputation_weight: 20,
+        });
+    }
+
+    #[ink::test]
+    fn flags_crossing_threshold_auto_suspend_proposal() {
+        use scale::Encode;
+        
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        contract.set_flag_threshold(Some(FlagThreshold::Absolute(2))).unwrap();
+        for voter in [accounts.alice, accounts.bob, accounts.charlie] {
+            set_caller(voter);
+            contract.register_voter().unwrap();
+        }
+        
+        set_caller(accounts.alice);
+        let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
+        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
+        
+        set_caller(accounts.bob);
+        contract.flag_proposal(proposal_id).unwrap();
+        assert_eq!(contract.flag_proposal(proposal_id).unwrap_err(), crate::errors::Error::AlreadyFlagged);
+        assert_eq!(contract.get_proposal(proposal_id).unwrap().status, ProposalStatus::Active);
+        
+        set_caller(accounts.charlie);
+        contract.flag_proposal(proposal_id).unwrap();
+        assert_eq!(contract.get_proposal(proposal_id).unwrap().status, ProposalStatus::Suspended);
+        assert_eq!(contract.get_flaggers(proposal_id, 0, 10).unwrap(), vec![accounts.bob, accounts.charlie]);
+        
+        let event = ink::env::test::recorded_events().into_iter().last().unwrap();
+        assert_eq!(event.data, (proposal_id, 2u32).encode());
+        
+        // Unregistered accounts cannot flag
+        set_caller(accounts.django);
+        assert_eq!(contract.flag_proposal(proposal_id).unwrap_err(), crate::errors::Error::NotRegistered);
+    }
+
+    #[ink::test]
+    fn guardian_resume_survives_further_flags() {
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        contract.set_flag_threshold(Some(FlagThreshold::Percentage(25))).unwrap();
+        for voter in [accounts.alice, accounts.bob, accounts.charlie, accounts.django] {
+            set_caller(voter);
+            contract.register_voter().unwrap();
+        }
+        
+        set_caller(accounts.alice);
+        let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
+        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
+        
+        // One flag out of four voters reaches 25%
+        set_caller(accounts.bob);
+        contract.flag_proposal(proposal_id).unwrap();
+        assert_eq!(contract.get_proposal(proposal_id).unwrap().status, ProposalStatus::Suspended);
+        
+        set_caller(accounts.alice);
+        contract.resume_proposal(proposal_id).unwrap();
+        
+        // Flags after the guardian's review do not suspend again
+        set_caller(accounts.charlie);
+        contract.flag_proposal(proposal_id).unwrap();
+        assert_eq!(contract.get_proposal(proposal_id).unwrap().status, ProposalStatus::Active);
+        assert_eq!(contract.get_flag_count(proposal_id), 2);
+    }
+
+    #[ink::test]
+    fn governance_can_bar_repeat_flagger() {
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        contract.register_voter().unwrap();
+        set_caller(accounts.bob);
+        contract.register_voter().unwrap();
+        
+        set_caller(accounts.alice);
+        enact_parameter_change(&mut contract, ParameterChange {
+            action: GovernanceAction::SetFlaggingBarred { account: accounts.bob, barred: true },
+            sunset_after: None,
+        });
+        assert!(contract.is_flagging_barred(accounts.bob));
+        
+        let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
+        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
+        
+        set_caller(accounts.bob);
+        assert_eq!(contract.flag_proposal(proposal_id).unwrap_err(), crate::errors::Error::FlaggingBarred);
+        assert_eq!(contract.get_flag_count(proposal_id), 0);
+    }
+
+    #[ink::test]
+    fn earliest_reached_breaks_tie_in_favour_of_first_option_to_reach_total() {
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        for voter in [accounts.alice, accounts.bob, accounts.charlie, accounts.django] {
+            set_caller(voter);
+            contract.register_voter().unwrap();
+        }
+        
+        set_caller(accounts.alice);
+        let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
+        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
+        
+        // "Yes" reaches two votes at t=200, "No" only at t=400
+        for (voter, timestamp, option_index, option_text) in [
+            (accounts.alice, 100, 0, "Yes"),
+            (accounts.bob, 150, 1, "No"),
+            (accounts.charlie, 200, 0, "Yes"),
+            (accounts.django, 400, 1, "No"),
+        ] {
+            set_caller(voter);
+            set_block_timestamp::<ink::env::DefaultEnvironment>(timestamp);
+            contract.vote(proposal_id, VoteChoice { option_index, option_text: option_text.to_string() }).unwrap();
+        }
+        
+        // The default policy rejects the tie
+        assert_eq!(contract.simulate_finalization(proposal_id).unwrap().failing_check, Some(FinalizationCheck::Tie));
+        
+        set_caller(accounts.alice);
+        contract.set_tie_break_policy(TieBreakPolicy::EarliestReached).unwrap();
+        let proposal = contract.get_proposal(proposal_id).unwrap();
+        set_block_timestamp::<ink::env::DefaultEnvironment>(proposal.voting_end + 1);
+        assert_eq!(contract.update_proposal_status(proposal_id).unwrap(), ProposalStatus::Passed);
+        assert_eq!(contract.get_winning_option(proposal_id).unwrap(), Some(("Yes".to_string(), 2)));
+    }
+
+    #[ink::test]
+    fn stored_ballot_is_compact_and_rebuilds_full_vote() {
+        use scale::Encode;
+        
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        contract.register_voter().unwrap();
+        let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
+        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
+        
+        set_block_timestamp::<ink::env::DefaultEnvironment>(500);
+        contract.vote(proposal_id, VoteChoice { option_index: 1, option_text: "No".to_string() }).unwrap();
+        
+        // The public shape is rebuilt from the key and the proposal's option text
+        let vote = contract.get_user_vote(proposal_id, accounts.alice).unwrap();
+        assert_eq!(vote, Vote {
+            voter: accounts.alice,
+            choice: VoteChoice { option_index: 1, option_text: "No".to_string() },
+            timestamp: 500,
+            weight: 1,
+        });
+        
+        // Dropping the voter and option text leaves 26 of the 51 bytes per ballot
+        let stored = StoredVote { option_index: 1, timestamp: 500, weight: 1 };
+        assert_eq!(vote.encode().len(), 51);
+        assert_eq!(stored.encode().len(), 26);
+    }
+
+    #[ink::test]
+    fn registration_stops_at_voter_cap() {
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        contract.set_max_voters(2).unwrap();
+        
+        contract.register_voter().unwrap();
+        contract.admin_register_voter(accounts.bob, false).unwrap();
+        assert_eq!(contract.get_total_voters(), 2);
+        
+        // Both self-registration and admin registration are rejected at the cap
+        assert_eq!(contract.admin_register_voter(accounts.charlie, false).unwrap_err(), crate::errors::Error::VoterCapReached);
+        set_caller(accounts.django);
+        assert_eq!(contract.register_voter().unwrap_err(), crate::errors::Error::VoterCapReached);
+        assert!(!contract.is_voter_registered(accounts.django));
+        assert_eq!(contract.get_total_voters(), 2);
+    }
+
+    #[ink::test]
+    fn lifecycle_hooks_run_in_order() {
+        use crate::treasurygovernance::hook_trace;
+        
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        hook_trace::take();
+        
+        let mut contract = TreasuryGovernance::new();
+        for voter in [accounts.alice, accounts.bob] {
+            set_caller(voter);
+            contract.register_voter().unwrap();
+        }
+        
+        set_caller(accounts.alice);
+        let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
+        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
+        for voter in [accounts.alice, accounts.bob] {
+            set_caller(voter);
+            contract.vote(proposal_id, VoteChoice { option_index: 0, option_text: "Yes".to_string() }).unwrap();
+        }
+        
+        let proposal = contract.get_proposal(proposal_id).unwrap();
+        set_block_timestamp::<ink::env::DefaultEnvironment>(proposal.voting_end + 1);
+        contract.update_proposal_status(proposal_id).unwrap();
+        set_block_timestamp::<ink::env::DefaultEnvironment>(proposal.execution_time);
+        contract.execute_proposal(proposal_id).unwrap();
+        
+        assert_eq!(hook_trace::take(), vec!["on_proposal_created", "on_vote_cast", "on_vote_cast", "on_finalized", "on_executed"]);
+    }
+
+    #[ink::test]
+    fn rejected_proposal_skips_execution_hook() {
+        use crate::treasurygovernance::hook_trace;
+        
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        hook_trace::take();
+        
+        let mut contract = TreasuryGovernance::new();
+        let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
+        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
+        
+        // Nobody votes, so the proposal is rejected and can never execute
+        let proposal = contract.get_proposal(proposal_id).unwrap();
+        set_block_timestamp::<ink::env::DefaultEnvironment>(proposal.voting_end + 1);
+        assert_eq!(contract.update_proposal_status(proposal_id).unwrap(), ProposalStatus::Rejected);
+        set_block_timestamp::<ink::env::DefaultEnvironment>(proposal.execution_time);
+        assert!(contract.execute_proposal(proposal_id).is_err());
+        
+        assert_eq!(hook_trace::take(), vec!["on_proposal_created", "on_finalized"]);
+    }
+
+    #[ink::test]
+    fn finalize_before_stops_at_boundary_id_and_max() {
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        contract.register_voter().unwrap();
+        
+        let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
+        let mut proposal_ids = Vec::new();
+        for _ in 0..4 {
+            proposal_ids.push(contract.create_proposal(title.clone(), description.clone(), proposal_type.clone(), governance_params.clone(), voting_options.clone(), vec![]).unwrap());
+        }
+        
+        // Only the owner may run the cleanup
+        set_caller(accounts.bob);
+        assert_eq!(contract.finalize_before(10, 10).unwrap_err(), crate::errors::Error::NotAuthorized);
+        
+        // Nothing has ended yet
+        set_caller(accounts.alice);
+        assert_eq!(contract.finalize_before(10, 10).unwrap(), 0);
+        
+        let proposal = contract.get_proposal(proposal_ids[0]).unwrap();
+        set_block_timestamp::<ink::env::DefaultEnvironment>(proposal.voting_end + 1);
+        
+        // IDs 1..=3 fall below the boundary, but only two may be finalized in this run
+        assert_eq!(contract.finalize_before(proposal_ids[3], 2).unwrap(), 2);
+        assert_eq!(contract.get_proposal(proposal_ids[0]).unwrap().status, ProposalStatus::Rejected);
+        assert_eq!(contract.get_proposal(proposal_ids[1]).unwrap().status, ProposalStatus::Rejected);
+        assert_eq!(contract.get_proposal(proposal_ids[2]).unwrap().status, ProposalStatus::Active);
+        
+        // The next run picks up the remaining old proposal and leaves the boundary ID alone
+        assert_eq!(contract.finalize_before(proposal_ids[3], 10).unwrap(), 1);
+        assert_eq!(contract.get_proposal(proposal_ids[2]).unwrap().status, ProposalStatus::Rejected);
+        assert_eq!(contract.get_proposal(proposal_ids[3]).unwrap().status, ProposalStatus::Active);
+    }
+
+    #[ink::test]
+    fn voter_info_tracks_registration_and_activity() {
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        set_block_timestamp::<ink::env::DefaultEnvironment>(100);
+        
+        let mut contract = TreasuryGovernance::new();
+        contract.register_voter().unwrap();
+        contract.admin_register_voter(accounts.bob, false).unwrap();
+        assert_eq!(contract.get_voter_info(accounts.bob).unwrap().registered_by, accounts.alice);
+        assert!(contract.get_voter_info(accounts.charlie).is_none());
+        
+        set_block_timestamp::<ink::env::DefaultEnvironment>(500);
+        let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
+        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
+        set_block_timestamp::<ink::env::DefaultEnvironment>(900);
+        contract.vote(proposal_id, VoteChoice { option_index: 0, option_text: "Yes".to_string() }).unwrap();
+        
+        assert_eq!(contract.get_voter_info(accounts.alice).unwrap(), VoterInfo {
+            registered_at: 100,
+            registered_by: accounts.alice,
+            tier: VoterTier::Member,
+            votes_cast: 1,
+            proposals_created: 1,
+            last_active_at: 900,
+        });
+        
+        contract.set_voter_tier(accounts.bob, VoterTier::Council).unwrap();
+        assert_eq!(contract.get_voter_info(accounts.bob).unwrap().tier, VoterTier::Council);
+        assert_eq!(contract.set_voter_tier(accounts.charlie, VoterTier::Council).unwrap_err(), crate::errors::Error::NotRegistered);
+    }
+
+    #[ink::test]
+    fn legacy_voter_entries_are_read_and_upgraded() {
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        contract.insert_legacy_voter_for_test(accounts.alice, 40);
+        contract.insert_legacy_voter_for_test(accounts.bob, 60);
+        
+        // Old-format entries read as members registered by themselves
+        assert!(contract.is_voter_registered(accounts.alice));
+        assert_eq!(contract.get_registration_time(accounts.bob), Some(60));
+        assert_eq!(contract.get_voter_info(accounts.bob).unwrap(), VoterInfo {
+            registered_at: 60,
+            registered_by: accounts.bob,
+            tier: VoterTier::Member,
+            votes_cast: 0,
+            proposals_created: 0,
+            last_active_at: 60,
+        });
+        assert_eq!(contract.register_voter().unwrap_err(), crate::errors::Error::AlreadyRegistered);
+        
+        // Activity upgrades an entry in place
+        set_block_timestamp::<ink::env::DefaultEnvironment>(1_000);
+        let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
+        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
+        contract.vote(proposal_id, VoteChoice { option_index: 0, option_text: "Yes".to_string() }).unwrap();
+        let alice = contract.get_voter_info(accounts.alice).unwrap();
+        assert_eq!((alice.registered_at, alice.votes_cast, alice.proposals_created), (40, 1, 1));
+        
+        // The explicit migration upgrades the remaining entry once
+        assert_eq!(contract.migrate_voter_records(vec![accounts.alice, accounts.bob, accounts.charlie]), 1);
+        assert_eq!(contract.migrate_voter_records(vec![accounts.bob]), 0);
+        assert_eq!(contract.get_registration_time(accounts.bob), Some(60));
+        assert_eq!(contract.get_total_voters(), 2);
+    }
+
+    #[ink::test]
+    fn voting_power_is_capped() {
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        contract.set_tenure_weight_per_day(10).unwrap();
+        contract.register_voter().unwrap();
+        
+        set_block_timestamp::<ink::env::DefaultEnvironment>(5 * MILLIS_PER_DAY);
+        assert_eq!(contract.get_voting_power(accounts.alice).unwrap(), 51);
+        
+        contract.set_max_effective_power(20).unwrap();
+        assert_eq!(contract.get_voting_power(accounts.alice).unwrap(), 20);
+        
+        let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
+        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
+        contract.vote(proposal_id, VoteChoice { option_index: 0, option_text: "Yes".to_string() }).unwrap();
+        assert_eq!(contract.get_user_vote(proposal_id, accounts.alice).unwrap().weight, 20);
+        assert_eq!(contract.get_proposal(proposal_id).unwrap().vote_counts, vec![20, 0]);
+    }
+
+    #[ink::test]
+    fn transfer_actions_only_on_treasury_proposals() {
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        let (title, description, _, governance_params, mut voting_options) = create_test_proposal_params();
+        voting_options.actions = vec![OutcomeAction::Transfer { recipient: accounts.bob, amount: 100 }, OutcomeAction::NoOp];
+        
+        let result = contract.create_proposal(title.clone(), description.clone(), ProposalType::Technical, governance_params.clone(), voting_options.clone(), vec![]);
+        assert_eq!(result.unwrap_err(), crate::errors::Error::InvalidProposal);
+        assert!(contract.create_proposal(title, description, ProposalType::Treasury, governance_params, voting_options, vec![]).is_ok());
+    }
+
+    #[ink::test]
+    fn treasury_proposals_can_be_required_to_carry_transfer() {
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
+        
+        // Signalling treasury proposals stay allowed until the owner opts in
+        assert!(contract.create_proposal(title.clone(), description.clone(), proposal_type.clone(), governance_params.clone(), voting_options.clone(), vec![]).is_ok());
+        
+        contract.set_require_treasury_actions(true).unwrap();
+        let result = contract.create_proposal(title.clone(), description.clone(), proposal_type.clone(), governance_params.clone(), voting_options.clone(), vec![]);
+        assert_eq!(result.unwrap_err(), crate::errors::Error::InvalidProposal);
+        
+        // Other proposal types need no action
+        assert!(contract.create_proposal(title, description, ProposalType::Other, governance_params, voting_options, vec![]).is_ok());
+    }
+
+    #[ink::test]
+    fn total_votes_cast_sums_weighted_counts() {
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        contract.set_tenure_weight_per_day(2).unwrap();
+        contract.register_voter().unwrap();
+        
+        set_block_timestamp::<ink::env::DefaultEnvironment>(3 * MILLIS_PER_DAY);
+        set_caller(accounts.bob);
+        contract.register_voter().unwrap();
+        
+        let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
+        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
+        assert_eq!(contract.get_total_votes_cast_on(proposal_id).unwrap(), 0);
+        
+        // Alice carries 1 + 3 days * 2 = 7, Bob only the base weight of 1
+        set_caller(accounts.alice);
+        contract.vote(proposal_id, VoteChoice { option_index: 0, option_text: "Yes".to_string() }).unwrap();
+        set_caller(accounts.bob);
+        contract.vote(proposal_id, VoteChoice { option_index: 1, option_text: "No".to_string() }).unwrap();
+        
+        assert_eq!(contract.get_total_votes_cast_on(proposal_id).unwrap(), 8);
+        assert_eq!(contract.get_proposal(proposal_id).unwrap().total_voters, 2);
+        assert_eq!(contract.get_total_votes_cast_on(99).unwrap_err(), crate::errors::Error::ProposalNotFound);
+    }
+
+    #[ink::test]
+    fn execution_waits_for_oracle_condition() {
+        use crate::treasurygovernance::oracle_responses::set_response;
+        
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        contract.register_voter().unwrap();
+        
+        let oracle = ink::primitives::H160::from([0x42; 20]);
+        let (title, description, proposal_type, mut governance_params, voting_options) = create_test_proposal_params();
+        governance_params.oracle = Some(OracleCondition { oracle, selector: [0xde, 0xad, 0xbe, 0xef] });
+        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
+        contract.vote(proposal_id, VoteChoice { option_index: 0, option_text: "Yes".to_string() }).unwrap();
+        
+        let proposal = contract.get_proposal(proposal_id).unwrap();
+        set_block_timestamp::<ink::env::DefaultEnvironment>(proposal.voting_end + 1);
+        contract.update_proposal_status(proposal_id).unwrap();
+        set_block_timestamp::<ink::env::DefaultEnvironment>(proposal.execution_time);
+        
+        // The condition does not hold yet, so the proposal stays passed
+        set_response(oracle, false);
+        assert_eq!(contract.execute_proposal(proposal_id).unwrap_err(), crate::errors::Error::OracleConditionNotMet);
+        assert_eq!(contract.simulate_execution(proposal_id).unwrap_err(), crate::errors::Error::OracleConditionNotMet);
+        assert_eq!(contract.get_proposal(proposal_id).unwrap().status, ProposalStatus::Passed);
+        
+        set_response(oracle, true);
+        contract.execute_proposal(proposal_id).unwrap();
+        assert_eq!(contract.get_proposal(proposal_id).unwrap().status, ProposalStatus::Executed);
+    }
+
+    #[ink::test]
+    fn oracle_condition_needs_an_oracle_address() {
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        let (title, description, proposal_type, mut governance_params, voting_options) = create_test_proposal_params();
+        governance_params.oracle = Some(OracleCondition { oracle: ink::primitives::H160::zero(), selector: [0; 4] });
+        let result = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]);
+        assert_eq!(result.unwrap_err(), crate::errors::Error::InvalidProposal);
+    }
+
+    #[ink::test]
+    fn assigned_weights_drive_tallies_and_events() {
+        use scale::Encode;
+        
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        for voter in [accounts.alice, accounts.bob, accounts.charlie] {
+            set_caller(voter);
+            contract.register_voter().unwrap();
+        }
+        
+        set_caller(accounts.alice);
+        contract.assign_voting_weight(accounts.alice, 5).unwrap();
+        contract.assign_voting_weight(accounts.bob, 3).unwrap();
+        assert_eq!(contract.get_assigned_weight(accounts.charlie), 1);
+        
+        let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
+        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
+        contract.vote(proposal_id, VoteChoice { option_index: 0, option_text: "Yes".to_string() }).unwrap();
+        let event = ink::env::test::recorded_events().into_iter().last().unwrap();
+        assert_eq!(event.data, (proposal_id, accounts.alice, 0u32, "Yes".to_string(), 5u128).encode());
+        
+        for voter in [accounts.bob, accounts.charlie] {
+            set_caller(voter);
+            contract.vote(proposal_id, VoteChoice { option_index: 1, option_text: "No".to_string() }).unwrap();
+        }
+        assert_eq!(contract.get_user_vote(proposal_id, accounts.bob).unwrap().weight, 3);
+        assert_eq!(contract.get_detailed_results(proposal_id).unwrap(), vec![("Yes".to_string(), 5), ("No".to_string(), 4)]);
+        
+        // Only the owner assigns weights
+        assert_eq!(contract.assign_voting_weight(accounts.charlie, 100).unwrap_err(), crate::errors::Error::NotAuthorized);
+    }
+
+    #[ink::test]
+    fn assigned_weights_overflowing_tally_are_rejected() {
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        for voter in [accounts.alice, accounts.bob] {
+            set_caller(voter);
+            contract.register_voter().unwrap();
+        }
+        
+        set_caller(accounts.alice);
+        contract.assign_voting_weight(accounts.alice, u128::MAX).unwrap();
+        contract.assign_voting_weight(accounts.bob, 1).unwrap();
+        let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
+        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
+        contract.vote(proposal_id, VoteChoice { option_index: 0, option_text: "Yes".to_string() }).unwrap();
+        
+        set_caller(accounts.bob);
+        let result = contract.vote(proposal_id, VoteChoice { option_index: 0, option_text: "Yes".to_string() });
+        assert_eq!(result.unwrap_err(), crate::errors::Error::InvalidProposal);
+        assert_eq!(contract.get_proposal(proposal_id).unwrap().vote_counts, vec![u128::MAX, 0]);
+    }
+
+    #[ink::test]
+    fn deposits_are_announced_and_counted_in_treasury_balance() {
+        use ink::primitives::U256;
+        use scale::Encode;
+        
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        let contract_account = ink::env::test::callee::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_account_balance(contract_account, U256::from(500u128));
+        assert_eq!(contract.get_treasury_balance(), 500);
+        
+        // The off-chain environment does not move the value, so credit it alongside the call
+        set_caller(accounts.bob);
+        ink::env::test::set_value_transferred(U256::from(1_500u128));
+        ink::env::test::set_account_balance(contract_account, U256::from(2_000u128));
+        contract.deposit_funds().unwrap();
+        
+        let event = ink::env::test::recorded_events().into_iter().last().unwrap();
+        assert_eq!(event.data, (accounts.bob, 1_500u128).encode());
+        assert_eq!(contract.get_treasury_balance(), 2_000);
+        assert_eq!(contract.get_dashboard().treasury_balance, 2_000);
+    }
+
+    #[ink::test]
+    fn zero_value_deposit_is_rejected() {
+        use ink::primitives::U256;
+        
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        ink::env::test::set_value_transferred(U256::zero());
+        assert_eq!(contract.deposit_funds().unwrap_err(), crate::errors::Error::InvalidAmount);
+    }
+
+    #[ink::test]
+    fn process_proposals_finalizes_and_executes_batch() {
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        contract.register_voter().unwrap();
+        
+        let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
+        let passing = contract.create_proposal(title.clone(), description.clone(), proposal_type.clone(), governance_params.clone(), voting_options.clone(), vec![]).unwrap();
+        let unsupported = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
+        contract.vote(passing, VoteChoice { option_index: 0, option_text: "Yes".to_string() }).unwrap();
+        
+        // Before voting ends nothing changes
+        assert_eq!(contract.process_proposals(vec![passing, unsupported]), vec![(passing, ProposalStatus::Active), (unsupported, ProposalStatus::Active)]);
+        
+        // Repeated and unknown IDs are skipped
+        let proposal = contract.get_proposal(passing).unwrap();
+        set_block_timestamp::<ink::env::DefaultEnvironment>(proposal.execution_time);
+        let processed = contract.process_proposals(vec![passing, unsupported, passing, 99]);
+        assert_eq!(processed, vec![(passing, ProposalStatus::Executed), (unsupported, ProposalStatus::Rejected)]);
+        assert_eq!(contract.get_proposal(passing).unwrap().status, ProposalStatus::Executed);
+    }
+
+    #[ink::test]
+    fn quorum_denominator_is_snapshotted_at_creation() {
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        for voter in [accounts.alice, accounts.bob, accounts.charlie, accounts.django] {
+            set_caller(voter);
+            contract.register_voter().unwrap();
+        }
+        
+        set_caller(accounts.alice);
+        let (title, description, proposal_type, mut governance_params, voting_options) = create_test_proposal_params();
+        governance_params.quorum_threshold = QuorumThreshold::TwentyFive;
+        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
+        assert_eq!(contract.get_proposal(proposal_id).unwrap().voters_snapshot, 4);
+        
+        // Eight more members join after the proposal opened
+        for byte in 1..=8u8 {
+            contract.admin_register_voter(ink::primitives::H160::from([byte; 20]), false).unwrap();
+        }
+        assert_eq!(contract.get_total_voters(), 12);
+        
+        // 25% of the four voters at creation still needs one vote, not three
+        contract.vote(proposal_id, VoteChoice { option_index: 0, option_text: "Yes".to_string() }).unwrap();
+        let (_, has_quorum, total_voters, required_votes) = contract.get_proposal_results(proposal_id).unwrap();
+        assert_eq!((has_quorum, total_voters, required_votes), (true, 1, 1));
+        assert!(contract.has_reached_quorum(proposal_id).unwrap());
+        
+        let proposal = contract.get_proposal(proposal_id).unwrap();
+        set_block_timestamp::<ink::env::DefaultEnvironment>(proposal.voting_end + 1);
+        assert_eq!(contract.update_proposal_status(proposal_id).unwrap(), ProposalStatus::Passed);
+    }
+
+    #[ink::test]
+    fn underfunded_treasury_transfer_stays_passed_until_funded() {
+        use ink::primitives::U256;
+        
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        let contract_account = ink::env::test::callee::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_account_balance(contract_account, U256::from(400u128));
+        contract.register_voter().unwrap();
+        
+        let (title, description, proposal_type, governance_params, mut voting_options) = create_test_proposal_params();
+        voting_options.actions = vec![OutcomeAction::Transfer { recipient: accounts.bob, amount: 1_000 }, OutcomeAction::NoOp];
+        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
+        contract.vote(proposal_id, VoteChoice { option_index: 0, option_text: "Yes".to_string() }).unwrap();
+        
+        let proposal = contract.get_proposal(proposal_id).unwrap();
+        set_block_timestamp::<ink::env::DefaultEnvironment>(proposal.voting_end + 1);
+        contract.update_proposal_status(proposal_id).unwrap();
+        set_block_timestamp::<ink::env::DefaultEnvironment>(proposal.execution_time);
+        
+        assert_eq!(contract.simulate_execution(proposal_id).unwrap_err(), crate::errors::Error::InsufficientTreasuryFunds);
+        assert_eq!(contract.execute_proposal(proposal_id).unwrap_err(), crate::errors::Error::InsufficientTreasuryFunds);
+        assert_eq!(contract.get_proposal(proposal_id).unwrap().status, ProposalStatus::Passed);
+        
+        // Once the treasury is topped up the payout can be retried
+        let balance_of = |account| ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(account).unwrap();
+        let bob_before = balance_of(accounts.bob);
+        ink::env::test::set_account_balance(contract_account, U256::from(1_500u128));
+        contract.execute_proposal(proposal_id).unwrap();
+        assert_eq!(balance_of(accounts.bob), bob_before + U256::from(1_000u128));
+        assert_eq!(contract.get_treasury_balance(), 500);
+        assert_eq!(contract.get_proposal(proposal_id).unwrap().status, ProposalStatus::Executed);
+    }
+
+    #[ink::test]
+    fn not_registered_is_distinct_from_not_authorized() {
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
+        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
+        
+        // A registered non-owner fails role checks with NotAuthorized
+        set_caller(accounts.bob);
+        contract.register_voter().unwrap();
+        assert_eq!(contract.set_max_voters(10).unwrap_err(), crate::errors::Error::NotAuthorized);
+        
+        // An unregistered caller fails voter checks with NotRegistered
+        set_caller(accounts.charlie);
+        let vote_choice = VoteChoice { option_index: 0, option_text: "Yes".to_string() };
+        assert_eq!(contract.vote(proposal_id, vote_choice).unwrap_err(), crate::errors::Error::NotRegistered);
+        assert_eq!(contract.endorse_proposal(proposal_id).unwrap_err(), crate::errors::Error::NotRegistered);
+    }
+
+    #[ink::test]
+    fn seven_day_voting_period_ends_after_604_800_000_ms() {
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        contract.register_voter().unwrap();
+        
+        let created_at: Timestamp = 1_700_000_000_000;
+        set_block_timestamp::<ink::env::DefaultEnvironment>(created_at);
+        let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
+        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
+        
+        let proposal = contract.get_proposal(proposal_id).unwrap();
+        assert_eq!(proposal.created_at, created_at);
+        assert_eq!(proposal.voting_end, created_at + 604_800_000);
+        assert_eq!(proposal.execution_time, proposal.voting_end + 86_400_000);
+        
+        // Voting stays open through the last millisecond of the period
+        set_block_timestamp::<ink::env::DefaultEnvironment>(created_at + 604_800_000);
+        contract.vote(proposal_id, VoteChoice { option_index: 0, option_text: "Yes".to_string() }).unwrap();
+        
+        set_block_timestamp::<ink::env::DefaultEnvironment>(created_at + 604_800_001);
+        set_caller(accounts.bob);
+        contract.register_voter().unwrap();
+        let result = contract.vote(proposal_id, VoteChoice { option_index: 0, option_text: "Yes".to_string() });
+        assert_eq!(result.unwrap_err(), crate::errors::Error::VotingPeriodEnded);
+    }
+
+    #[ink::test]
+    fn authors_or_owner_cancel_active_proposals() {
+        use scale::Encode;
+        
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        for voter in [accounts.alice, accounts.bob, accounts.charlie] {
+            set_caller(voter);
+            contract.register_voter().unwrap();
+        }
+        
+        set_caller(accounts.bob);
+        let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
+        let mut cancellable = governance_params.clone();
+        cancellable.allow_cancel_after_votes = true;
+        let proposal_id = contract.create_proposal(title.clone(), description.clone(), proposal_type.clone(), cancellable, voting_options.clone(), vec![accounts.charlie]).unwrap();
+        contract.vote(proposal_id, VoteChoice { option_index: 0, option_text: "Yes".to_string() }).unwrap();
+        
+        // Other voters cannot cancel, a co-author can
+        set_caller(accounts.django);
+        assert_eq!(contract.cancel_proposal(proposal_id).unwrap_err(), crate::errors::Error::NotAuthorized);
+        set_caller(accounts.charlie);
+        contract.cancel_proposal(proposal_id).unwrap();
+        
+        let event = ink::env::test::recorded_events().into_iter().last().unwrap();
+        assert_eq!(event.data, (proposal_id, accounts.charlie).encode());
+        assert_eq!(contract.get_proposal(proposal_id).unwrap().status, ProposalStatus::Cancelled);
+        
+        // Further votes are rejected while the ballots already cast stay queryable
+        let result = contract.vote(proposal_id, VoteChoice { option_index: 0, option_text: "Yes".to_string() });
+        assert_eq!(result.unwrap_err(), crate::errors::Error::ProposalNotActive);
+        assert_eq!(contract.get_user_vote(proposal_id, accounts.bob).unwrap().weight, 1);
+        assert_eq!(contract.cancel_proposal(proposal_id).unwrap_err(), crate::errors::Error::ProposalNotActive);
+        
+        // The owner may cancel, but not once voting has ended
+        set_caller(accounts.bob);
+        let second = contract.create_proposal(title.clone(), description.clone(), proposal_type.clone(), governance_params.clone(), voting_options.clone(), vec![]).unwrap();
+        let third = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
+        set_caller(accounts.alice);
+        contract.cancel_proposal(second).unwrap();
+        
+        let voting_end = contract.get_proposal(third).unwrap().voting_end;
+        set_block_timestamp::<ink::env::DefaultEnvironment>(voting_end + 1);
+        assert_eq!(contract.cancel_proposal(third).unwrap_err(), crate::errors::Error::VotingPeriodEnded);
+    }
+
+    #[ink::test]
+    fn effective_proposal_count_excludes_cancelled_drafts() {
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        contract.register_voter().unwrap();
+        let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
+        let draft = contract.create_proposal(title.clone(), description.clone(), proposal_type.clone(), governance_params.clone(), voting_options.clone(), vec![]).unwrap();
+        contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
+        
+        // A draft withdrawn before any votes keeps its ID but is not a real proposal
+        contract.cancel_proposal(draft).unwrap();
+        assert_eq!(contract.get_proposal_count(), 2);
+        assert_eq!(contract.get_effective_proposal_count(), 1);
+        assert_eq!(contract.get_next_proposal_id(), 3);
+    }
+
+    #[ink::test]
+    fn batch_finalization_emits_summary() {
+        use scale::Encode;
+        
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        contract.register_voter().unwrap();
+        
+        let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
+        let mut proposal_ids = Vec::new();
+        for _ in 0..5 {
+            proposal_ids.push(contract.create_proposal(title.clone(), description.clone(), proposal_type.clone(), governance_params.clone(), voting_options.clone(), vec![]).unwrap());
+        }
+        contract.vote(proposal_ids[0], VoteChoice { option_index: 0, option_text: "Yes".to_string() }).unwrap();
+        contract.vote(proposal_ids[3], VoteChoice { option_index: 0, option_text: "Yes".to_string() }).unwrap();
+        
+        let voting_end = contract.get_proposal(proposal_ids[0]).unwrap().voting_end;
+        set_block_timestamp::<ink::env::DefaultEnvironment>(voting_end + 1);
+        
+        // One passed and two rejected proposals are summarized in a single event
+        assert_eq!(contract.finalize_before(proposal_ids[3], 10).unwrap(), 3);
+        let event = ink::env::test::recorded_events().into_iter().last().unwrap();
+        assert_eq!(event.data, (3u32, 1u32, 2u32).encode());
+        
+        // Keeper batches count only the proposals they finalized themselves
+        contract.process_proposals(vec![proposal_ids[0], proposal_ids[3], proposal_ids[4]]);
+        let summary = ink::env::test::recorded_events()
+            .filter(|event| event.data == (2u32, 1u32, 1u32).encode())
+            .count();
+        assert_eq!(summary, 1);
+        
+        // A batch that finalizes nothing emits no summary
+        let before = ink::env::test::recorded_events().count();
+        assert_eq!(contract.finalize_before(10, 10).unwrap(), 0);
+        assert_eq!(ink::env::test::recorded_events().count(), before);
+    }
+
+    #[ink::test]
+    fn delegates_vote_with_delegated_weight() {
+        use scale::Encode;
+        
+        let accounts = default_accounts();
+        let mut contract = TreasuryGovernance::new();
+        for voter in [accounts.alice, accounts.bob, accounts.charlie] {
+            set_caller(voter);
+            contract.register_voter().unwrap();
+        }
+        
+        // Self-delegation and unregistered delegates are rejected
+        assert_eq!(contract.delegate_to(accounts.charlie).unwrap_err(), crate::errors::Error::InvalidDelegation);
+        assert_eq!(contract.delegate_to(accounts.django).unwrap_err(), crate::errors::Error::InvalidDelegation);
+        assert_eq!(contract.revoke_delegation().unwrap_err(), crate::errors::Error::InvalidDelegation);
+        
+        contract.delegate_to(accounts.alice).unwrap();
+        set_caller(accounts.bob);
+        contract.delegate_to(accounts.alice).unwrap();
+        assert_eq!(contract.get_delegate(accounts.bob), Some(accounts.alice));
+        assert_eq!(contract.get_delegated_weight(accounts.alice).unwrap(), 2);
+        assert_eq!(contract.get_voting_power(accounts.alice).unwrap(), 3);
+        
+        set_caller(accounts.alice);
+        let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
+        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
+        contract.vote(proposal_id, VoteChoice { option_index: 0, option_text: "Yes".to_string() }).unwrap();
+        
+        let event = ink::env::test::recorded_events().into_iter().last().unwrap();
+        assert_eq!(event.data, (proposal_id, accounts.alice, 0u32, "Yes".to_string(), 3u128).encode());
+        assert_eq!(contract.get_user_vote(proposal_id, accounts.alice).unwrap().weight, 3);
+        assert_eq!(contract.get_detailed_results(proposal_id).unwrap()[0].1, 3);
+        
+        // Delegators whose weight was cast cannot vote again themselves
+        set_caller(accounts.bob);
+        let result = contract.vote(proposal_id, VoteChoice { option_index: 1, option_text: "No".to_string() });
+        assert_eq!(result.unwrap_err(), crate::errors::Error::AlreadyVoted);
+        
+        // The power cap applies to the combined weight
+        set_caller(accounts.alice);
+        contract.set_max_effective_power(2).unwrap();
+        assert_eq!(contract.get_voting_power(accounts.alice).unwrap(), 2);
+    }
+
+    #[ink::test]
+    fn circular_delegation_rejected() {
+        let accounts = default_accounts();
+        let mut contract = TreasuryGovernance::new();
+        for voter in [accounts.alice, accounts.bob, accounts.charlie] {
+            set_caller(voter);
+            contract.register_voter().unwrap();
+        }
+        
+        // alice -> bob; bob cannot pass alice's weight on to charlie, which would orphan it
+        set_caller(accounts.alice);
+        contract.delegate_to(accounts.bob).unwrap();
+        set_caller(accounts.bob);
+        assert_eq!(contract.delegate_to(accounts.charlie).unwrap_err(), crate::errors::Error::InvalidDelegation);
+        
+        // Closing the loop back to alice is not allowed either
+        assert_eq!(contract.delegate_to(accounts.alice).unwrap_err(), crate::errors::Error::InvalidDelegation);
+        set_caller(accounts.charlie);
+        assert_eq!(contract.delegate_to(accounts.alice).unwrap_err(), crate::errors::Error::InvalidDelegation);
+        
+        // Bob keeps alice's weight and charlie carries none
+        assert_eq!(contract.get_delegated_weight(accounts.bob).unwrap(), 1);
+        assert_eq!(contract.get_delegated_weight(accounts.charlie).unwrap(), 0);
+        
+        // Once alice revokes, bob may delegate in turn
+        set_caller(accounts.alice);
+        contract.revoke_delegation().unwrap();
+        set_caller(accounts.bob);
+        contract.delegate_to(accounts.charlie).unwrap();
+        assert_eq!(contract.get_delegated_weight(accounts.charlie).unwrap(), 1);
+    }
+
+    #[ink::test]
+    fn delegation_changes_only_affect_later_ballots() {
+        let accounts = default_accounts();
+        let mut contract = TreasuryGovernance::new();
+        for voter in [accounts.alice, accounts.bob, accounts.charlie] {
+            set_caller(voter);
+            contract.register_voter().unwrap();
+        }
+        
+        set_caller(accounts.alice);
+        let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
+        let first = contract.create_proposal(title.clone(), description.clone(), proposal_type.clone(), governance_params.clone(), voting_options.clone(), vec![]).unwrap();
+        let second = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
+        
+        set_caller(accounts.bob);
+        contract.delegate_to(accounts.alice).unwrap();
+        set_caller(accounts.alice);
+        contract.vote(first, VoteChoice { option_index: 0, option_text: "Yes".to_string() }).unwrap();
+        
+        // Moving the delegation keeps bob's weight on the ballot that already carried it
+        set_caller(accounts.bob);
+        contract.revoke_delegation().unwrap();
+        contract.delegate_to(accounts.charlie).unwrap();
+        assert_eq!(contract.get_delegated_weight(accounts.alice).unwrap(), 0);
+        
+        set_caller(accounts.charlie);
+        contract.vote(first, VoteChoice { option_index: 1, option_text: "No".to_string() }).unwrap();
+        contract.vote(second, VoteChoice { option_index: 1, option_text: "No".to_string() }).unwrap();
+        assert_eq!(contract.get_detailed_results(first).unwrap(), vec![("Yes".to_string(), 2), ("No".to_string(), 1)]);
+        assert_eq!(contract.get_detailed_results(second).unwrap()[1].1, 2);
+        
+        // A delegator who votes before their delegate keeps their own ballot
+        set_caller(accounts.alice);
+        let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
+        let third = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
+        set_caller(accounts.bob);
+        contract.vote(third, VoteChoice { option_index: 0, option_text: "Yes".to_string() }).unwrap();
+        set_caller(accounts.charlie);
+        contract.vote(third, VoteChoice { option_index: 1, option_text: "No".to_string() }).unwrap();
+        assert_eq!(contract.get_detailed_results(third).unwrap(), vec![("Yes".to_string(), 1), ("No".to_string(), 1)]);
+    }
+
+    #[ink::test]
+    fn change_vote_moves_ballot_to_new_option() {
+        use ink::env::hash::Blake2x256;
+        use scale::Encode;
+        let accounts = default_accounts();
+        let mut contract = TreasuryGovernance::new();
+        for voter in [accounts.alice, accounts.bob] {
+            set_caller(voter);
+            contract.register_voter().unwrap();
+        }
+        
+        set_caller(accounts.alice);
+        let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
+        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
+        contract.vote(proposal_id, VoteChoice { option_index: 0, option_text: "Yes".to_string() }).unwrap();
+        
+        // Only existing ballots can move, and only to a valid option
+        set_caller(accounts.bob);
+        assert_eq!(contract.change_vote(proposal_id, VoteChoice { option_index: 1, option_text: "No".to_string() }), Err(Error::NotVoted));
+        set_caller(accounts.alice);
+        assert_eq!(contract.change_vote(proposal_id, VoteChoice { option_index: 5, option_text: "Maybe".to_string() }), Err(Error::InvalidProposal));
+        
+        contract.change_vote(proposal_id, VoteChoice { option_index: 1, option_text: "No".to_string() }).unwrap();
+        assert_eq!(contract.get_detailed_results(proposal_id).unwrap(), vec![("Yes".to_string(), 0), ("No".to_string(), 1)]);
+        assert_eq!(contract.get_proposal(proposal_id).unwrap().total_voters, 1);
+        assert_eq!(contract.get_user_vote(proposal_id, accounts.alice).unwrap().choice.option_index, 1);
+        
+        let event = ink::env::test::recorded_events().into_iter().last().unwrap();
+        assert_eq!(event.data, (proposal_id, accounts.alice, 0u32, 1u32).encode());
+        
+        // The ballot commitment logs the change as a second entry after the original ballot
+        let mut expected = [0u8; 32];
+        for option_index in [0u32, 1] {
+            let leaf_input = [accounts.alice.as_bytes(), &option_index.to_le_bytes(), &1u128.to_le_bytes()].concat();
+            let mut leaf = [0u8; 32];
+            ink::env::hash_bytes::<Blake2x256>(&leaf_input, &mut leaf);
+            let chained = [expected, leaf].concat();
+            ink::env::hash_bytes::<Blake2x256>(&chained, &mut expected);
+        }
+        assert_eq!(contract.get_ballot_commitment(proposal_id).unwrap(), BallotCommitment { commitment: expected, ballots: 2, sealed: false });
+    }
+
+    #[ink::test]
+    fn pass_conditions_report_each_check_separately() {
+        let accounts = default_accounts();
+        let mut contract = TreasuryGovernance::new();
+        for voter in [accounts.alice, accounts.bob, accounts.charlie] {
+            set_caller(voter);
+            contract.register_voter().unwrap();
+        }
+        set_caller(accounts.alice);
+        contract.set_min_distinct_participants(2).unwrap();
+        
+        let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
+        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
+        assert_eq!(contract.get_pass_conditions(proposal_id), Ok((false, false, false)));
+        
+        // A single ballot meets the 10% quorum and names a winner, but not the participation minimum
+        contract.vote(proposal_id, VoteChoice { option_index: 0, option_text: "Yes".to_string() }).unwrap();
+        assert_eq!(contract.get_pass_conditions(proposal_id), Ok((true, false, true)));
+        
+        set_caller(accounts.bob);
+        contract.vote(proposal_id, VoteChoice { option_index: 0, option_text: "Yes".to_string() }).unwrap();
+        assert_eq!(contract.get_pass_conditions(proposal_id), Ok((true, true, true)));
+        assert_eq!(contract.get_pass_conditions(99), Err(Error::ProposalNotFound));
+    }
+
+    #[ink::test]
+    fn repeated_vote_changes_keep_tallies_consistent() {
+        let accounts = default_accounts();
+        let mut contract = TreasuryGovernance::new();
+        for voter in [accounts.alice, accounts.bob] {
+            set_caller(voter);
+            contract.register_voter().unwrap();
+        }
+        
+        set_caller(accounts.alice);
+        let (title, description, proposal_type, governance_params, _) = create_test_proposal_params();
+        let voting_options = VotingOptions {
+            options: vec!["A".to_string(), "B".to_string(), "C".to_string()],
+            payloads: vec![],
+            actions: vec![],
+            abstain_index: None,
+        };
+        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
+        contract.vote(proposal_id, VoteChoice { option_index: 0, option_text: "A".to_string() }).unwrap();
+        set_caller(accounts.bob);
+        contract.vote(proposal_id, VoteChoice { option_index: 1, option_text: "B".to_string() }).unwrap();
+        
+        set_caller(accounts.alice);
+        assert_eq!(contract.change_vote(proposal_id, VoteChoice { option_index: 0, option_text: "A".to_string() }), Err(Error::SameVote));
+        contract.change_vote(proposal_id, VoteChoice { option_index: 2, option_text: "C".to_string() }).unwrap();
+        contract.change_vote(proposal_id, VoteChoice { option_index: 1, option_text: "B".to_string() }).unwrap();
+        set_caller(accounts.bob);
+        contract.change_vote(proposal_id, VoteChoice { option_index: 2, option_text: "C".to_string() }).unwrap();
+        
+        let counts = contract.get_proposal_results(proposal_id).unwrap().0;
+        assert_eq!(counts, vec![0, 1, 1]);
+        assert_eq!(counts.iter().sum::<u128>(), 2);
+        assert_eq!(contract.get_proposal(proposal_id).unwrap().total_voters, 2);
+        
+        // No changes once the voting period is over
+        set_block_timestamp::<ink::env::DefaultEnvironment>(8 * MILLIS_PER_DAY);
+        assert_eq!(contract.change_vote(proposal_id, VoteChoice { option_index: 0, option_text: "A".to_string() }), Err(Error::VotingPeriodEnded));
+    }
+
+    #[ink::test]
+    fn passed_proposals_expire_after_grace_period() {
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        let mut contract = TreasuryGovernance::new();
+        contract.register_voter().unwrap();
+        
+        let (title, description, proposal_type, mut governance_params, voting_options) = create_test_proposal_params();
+        governance_params.grace_period = Some(MILLIS_PER_DAY);
+        let inside = contract.create_proposal(title.clone(), description.clone(), proposal_type.clone(), governance_params.clone(), voting_options.clone(), vec![]).unwrap();
+        let outside = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
+        for proposal_id in [inside, outside] {
+            contract.vote(proposal_id, VoteChoice { option_index: 0, option_text: "Yes".to_string() }).unwrap();
+        }
+        
+        let proposal = contract.get_proposal(inside).unwrap();
+        set_block_timestamp::<ink::env::DefaultEnvironment>(proposal.voting_end + 1);
+        contract.update_proposal_status(inside).unwrap();
+        contract.update_proposal_status(outside).unwrap();
+        
+        // The last moment of the grace period still executes, and nothing can be expired yet
+        let deadline = proposal.execution_time + MILLIS_PER_DAY;
+        set_block_timestamp::<ink::env::DefaultEnvironment>(deadline);
+        assert_eq!(contract.expire_proposal(outside), Err(Error::ProposalNotReadyForExecution));
+        contract.execute_proposal(inside).unwrap();
+        
+        set_block_timestamp::<ink::env::DefaultEnvironment>(deadline + 1);
+        assert_eq!(contract.execute_proposal(outside), Err(Error::ProposalExpired));
+        contract.expire_proposal(outside).unwrap();
+        assert_eq!(contract.get_proposal(outside).unwrap().status, ProposalStatus::Expired);
+        assert_eq!(contract.execute_proposal(outside), Err(Error::ProposalExpired));
+        assert_eq!(contract.expire_proposal(inside), Err(Error::ProposalNotReadyForExecution));
+    }
+
+    #[ink::test]
+    fn cancellation_defaults_to_before_the_first_vote() {
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        let mut contract = TreasuryGovernance::new();
+        contract.register_voter().unwrap();
+        
+        let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
+        let voted = contract.create_proposal(title.clone(), description.clone(), proposal_type.clone(), governance_params.clone(), voting_options.clone(), vec![]).unwrap();
+        let unvoted = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
+        contract.vote(voted, VoteChoice { option_index: 0, option_text: "Yes".to_string() }).unwrap();
+        
+        assert_eq!(contract.cancel_proposal(voted), Err(Error::VotesAlreadyCast));
+        contract.cancel_proposal(unvoted).unwrap();
+        assert_eq!(contract.get_stats(), (2, 1, 0, 0, 0));
+        
+        // Cancelled proposals can be neither finalized nor executed
+        let voting_end = contract.get_proposal(unvoted).unwrap().voting_end;
+        set_block_timestamp::<ink::env::DefaultEnvironment>(voting_end + 1);
+        assert_eq!(contract.update_proposal_status(unvoted), Err(Error::ProposalNotActive));
+        assert_eq!(contract.execute_proposal(unvoted), Err(Error::ProposalNotActive));
+        assert_eq!(contract.vote(unvoted, VoteChoice { option_index: 0, option_text: "Yes".to_string() }), Err(Error::ProposalNotActive));
+    }
+
+    #[ink::test]
+    fn proposals_ranked_by_participation() {
+        let accounts = default_accounts();
+        let mut contract = TreasuryGovernance::new();
+        for voter in [accounts.alice, accounts.bob, accounts.charlie] {
+            set_caller(voter);
+            contract.register_voter().unwrap();
+        }
+        
+        set_caller(accounts.alice);
+        let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
+        let ids: Vec<u32> = (0..4)
+            .map(|_| contract.create_proposal(title.clone(), description.clone(), proposal_type.clone(), governance_params.clone(), voting_options.clone(), vec![]).unwrap())
+            .collect();
+        
+        // Turnout of 1, 3, 0 and 2 voters
+        let turnout = [(ids[0], vec![accounts.alice]), (ids[1], vec![accounts.alice, accounts.bob, accounts.charlie]), (ids[3], vec![accounts.bob, accounts.charlie])];
+        for (proposal_id, voters) in turnout {
+            for voter in voters {
+                set_caller(voter);
+                contract.vote(proposal_id, VoteChoice { option_index: 0, option_text: "Yes".to_string() }).unwrap();
+            }
+        }
+        
+        assert_eq!(contract.get_proposals_by_participation(10), vec![(ids[1], 3), (ids[3], 2), (ids[0], 1), (ids[2], 0)]);
+        assert_eq!(contract.get_proposals_by_participation(2), vec![(ids[1], 3), (ids[3], 2)]);
+        assert!(contract.get_proposals_by_participation(0).is_empty());
+    }
+
+    #[ink::test]
+    fn heavy_staker_outvotes_several_small_ones() {
+        use ink::primitives::U256;
+        
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        let mut contract = TreasuryGovernance::new();
+        contract.set_weight_source(WeightSource::Stake).unwrap();
+        
+        // The off-chain environment does not move the value, so credit the balance alongside
+        let contract_account = ink::env::test::callee::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_value_transferred(U256::from(1_000u128));
+        contract.register_voter().unwrap();
+        for voter in [accounts.bob, accounts.charlie, accounts.django] {
+            set_caller(voter);
+            ink::env::test::set_value_transferred(U256::from(100u128));
+            contract.register_voter().unwrap();
+        }
+        set_caller(accounts.bob);
+        contract.increase_stake().unwrap();
+        ink::env::test::set_value_transferred(U256::zero());
+        assert_eq!(contract.increase_stake(), Err(Error::InvalidAmount));
+        set_caller(accounts.eve);
+        contract.register_voter().unwrap();
+        
+        assert_eq!(contract.get_voting_power(accounts.alice), Ok(1_000));
+        assert_eq!(contract.get_voting_power(accounts.bob), Ok(200));
+        assert_eq!(contract.get_total_weight(), 1_400);
+        
+        // Stakes are held apart from the treasury
+        ink::env::test::set_account_balance(contract_account, U256::from(1_900u128));
+        assert_eq!(contract.get_treasury_balance(), 500);
+        
+        set_caller(accounts.alice);
+        let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
+        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
+        contract.vote(proposal_id, VoteChoice { option_index: 0, option_text: "Yes".to_string() }).unwrap();
+        for voter in [accounts.bob, accounts.charlie, accounts.django] {
+            set_caller(voter);
+            contract.vote(proposal_id, VoteChoice { option_index: 1, option_text: "No".to_string() }).unwrap();
+        }
+        
+        // An unstaked voter carries no weight
+        set_caller(accounts.eve);
+        assert_eq!(contract.vote(proposal_id, VoteChoice { option_index: 1, option_text: "No".to_string() }), Err(Error::InsufficientVotingPower));
+        
+        assert_eq!(contract.get_proposal_results(proposal_id).unwrap().0, vec![1_000, 400]);
+        let proposal = contract.get_proposal(proposal_id).unwrap();
+        set_block_timestamp::<ink::env::DefaultEnvironment>(proposal.voting_end + 1);
+        contract.update_proposal_status(proposal_id).unwrap();
+        assert_eq!(contract.get_proposal(proposal_id).unwrap().status, ProposalStatus::Passed);
+    }
+
+    #[ink::test]
+    fn abstain_counts_toward_quorum_but_never_wins() {
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        let voters = [accounts.alice, accounts.bob, accounts.charlie, accounts.django, accounts.eve, accounts.frank, ink::primitives::H160::from([0x11; 20]), ink::primitives::H160::from([0x12; 20])];
+        for voter in voters {
+            set_caller(voter);
+            contract.register_voter().unwrap();
+        }
+        
+        set_caller(accounts.alice);
+        let (title, description, proposal_type, mut governance_params, _) = create_test_proposal_params();
+        governance_params.quorum_threshold = QuorumThreshold::TwentyFive;
+        let mut voting_options = VotingOptions {
+            options: vec!["Yes".to_string(), "No".to_string(), "Abstain".to_string()],
+            payloads: vec![],
+            actions: vec![],
+            abstain_index: Some(3),
+        };
+        let result = contract.create_proposal(title.clone(), description.clone(), proposal_type.clone(), governance_params.clone(), voting_options.clone(), vec![]);
+        assert_eq!(result, Err(Error::InvalidProposal));
+        voting_options.abstain_index = Some(2);
+        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
+        
+        // One ballot alone misses the quorum of two
+        contract.vote(proposal_id, VoteChoice { option_index: 0, option_text: "Yes".to_string() }).unwrap();
+        assert_eq!(contract.get_pass_conditions(proposal_id), Ok((false, true, true)));
+        
+        // Abstains reach quorum while the leading abstain tally is passed over
+        for voter in [accounts.bob, accounts.charlie] {
+            set_caller(voter);
+            contract.vote(proposal_id, VoteChoice { option_index: 2, option_text: "Abstain".to_string() }).unwrap();
+        }
+        assert_eq!(contract.get_proposal(proposal_id).unwrap().total_voters, 3);
+        assert_eq!(contract.get_winning_option(proposal_id), Ok(Some(("Yes".to_string(), 1))));
+        
+        let voting_end = contract.get_proposal(proposal_id).unwrap().voting_end;
+        set_block_timestamp::<ink::env::DefaultEnvironment>(voting_end + 1);
+        assert_eq!(contract.update_proposal_status(proposal_id), Ok(ProposalStatus::Passed));
+    }
+
+    #[ink::test]
+    fn length_minimums_apply_per_proposal_type() {
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        contract.register_voter().unwrap();
+        contract.set_length_minimums(ProposalType::Technical, LengthMinimums { min_title: 5, min_description: 100 }).unwrap();
+        contract.set_length_minimums(ProposalType::Other, LengthMinimums { min_title: 5, min_description: 10 }).unwrap();
+        assert_eq!(contract.set_length_minimums(ProposalType::Other, LengthMinimums { min_title: MAX_TITLE_LENGTH as u32 + 1, min_description: 0 }), Err(Error::InvalidProposal));
+        
+        let (title, _, _, governance_params, voting_options) = create_test_proposal_params();
+        let description = "Upgrade the runtime".to_string();
+        let result = contract.create_proposal(title.clone(), description.clone(), ProposalType::Technical, governance_params.clone(), voting_options.clone(), vec![]);
+        assert_eq!(result, Err(Error::InvalidProposal));
+        assert!(contract.create_proposal(title, description, ProposalType::Other, governance_params, voting_options, vec![]).is_ok());
+    }
+
+    #[ink::test]
+    fn proposal_pages_cover_every_id_once() {
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        contract.register_voter().unwrap();
+        let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
+        for _ in 0..120 {
+            contract.create_proposal(title.clone(), description.clone(), proposal_type.clone(), governance_params.clone(), voting_options.clone(), vec![]).unwrap();
+        }
+        
+        // Oversized pages are capped and each page resumes after the last ID seen
+        let mut seen = Vec::new();
+        let mut start_id = 1;
+        loop {
+            let page = contract.get_proposal_ids(start_id, 100);
+            let Some(&last) = page.last() else {
+                break;
+            };
+            assert!(page.len() <= MAX_PAGE_SIZE as usize);
+            assert_eq!(contract.list_proposals(start_id, 100).into_iter().map(|proposal| proposal.id).collect::<Vec<u32>>(), page);
+            seen.extend(page);
+            start_id = last + 1;
+        }
+        assert_eq!(seen, (1..=120).collect::<Vec<u32>>());
+        assert_eq!(contract.get_proposal_ids(101, 50).len(), 20);
+        
+        // Past the last proposal or with a zero limit, pages are empty
+        assert!(contract.get_proposal_ids(121, 10).is_empty());
+        assert!(contract.list_proposals(121, 10).is_empty());
+        assert!(contract.get_proposal_ids(1, 0).is_empty());
+        assert!(contract.list_proposals(1, 0).is_empty());
+    }
+
+    #[ink::test]
+    fn register_and_vote_in_one_call() {
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        contract.register_voter().unwrap();
+        let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
+        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
+        
+        set_caller(accounts.bob);
+        contract.register_and_vote(proposal_id, VoteChoice { option_index: 1, option_text: "No".to_string() }).unwrap();
+        assert!(contract.is_voter_registered(accounts.bob));
+        assert_eq!(contract.get_total_voters(), 2);
+        assert_eq!(contract.get_detailed_results(proposal_id).unwrap()[1].1, 1);
+        
+        // Registered voters just vote, and the usual vote checks still apply
+        set_caller(accounts.alice);
+        contract.register_and_vote(proposal_id, VoteChoice { option_index: 0, option_text: "Yes".to_string() }).unwrap();
+        assert_eq!(contract.register_and_vote(proposal_id, VoteChoice { option_index: 0, option_text: "Yes".to_string() }), Err(Error::AlreadyVoted));
+        assert_eq!(contract.get_total_voters(), 2);
+        
+        // Probation applies to accounts registering on the spot
+        contract.set_voting_probation(MILLIS_PER_DAY, true).unwrap();
+        set_caller(accounts.charlie);
+        assert_eq!(contract.register_and_vote(proposal_id, VoteChoice { option_index: 0, option_text: "Yes".to_string() }), Err(Error::VoterInProbation));
+    }
+
+    #[ink::test]
+    fn registration_round_trip_emits_events() {
+        use scale::Encode;
+        
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        contract.register_voter().unwrap();
+        let event = ink::env::test::recorded_events().into_iter().last().unwrap();
+        assert_eq!(event.data, accounts.alice.encode());
+        set_caller(accounts.bob);
+        contract.register_voter().unwrap();
+        contract.delegate_to(accounts.alice).unwrap();
+        contract.insert_legacy_voter_for_test(accounts.charlie, 0);
+        assert_eq!(contract.get_total_voters(), 3);
+        
+        // Leaving ends the delegations to the departing voter
+        set_caller(accounts.alice);
+        contract.deregister_voter().unwrap();
+        let event = ink::env::test::recorded_events().into_iter().last().unwrap();
+        assert_eq!(event.data, accounts.alice.encode());
+        assert!(!contract.is_voter_registered(accounts.alice));
+        assert_eq!(contract.get_delegate(accounts.bob), None);
+        assert_eq!(contract.get_total_voters(), 2);
+        assert_eq!(contract.deregister_voter(), Err(Error::NotRegistered));
+        
+        // Legacy registrations can leave too
+        set_caller(accounts.charlie);
+        contract.deregister_voter().unwrap();
+        assert_eq!(contract.get_total_voters(), 1);
+        
+        set_caller(accounts.alice);
+        contract.register_voter().unwrap();
+        assert_eq!(contract.get_total_voters(), 2);
+        assert_eq!(contract.get_delegated_weight(accounts.alice), Ok(0));
+    }
+
+    #[ink::test]
+    fn misaligned_tally_is_refused() {
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        contract.register_voter().unwrap();
+        let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
+        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
+        contract.vote(proposal_id, VoteChoice { option_index: 0, option_text: "Yes".to_string() }).unwrap();
+        
+        // Three counts for two options would attach counts to the wrong labels
+        contract.corrupt_tally_for_test(proposal_id, vec![0, 0, 1]);
+        assert_eq!(contract.get_proposal_results(proposal_id).unwrap_err(), Error::InvalidProposal);
+        assert_eq!(contract.get_detailed_results(proposal_id).unwrap_err(), Error::InvalidProposal);
+        assert_eq!(contract.get_winning_option(proposal_id).unwrap_err(), Error::InvalidProposal);
+        assert_eq!(contract.simulate_finalization(proposal_id).unwrap_err(), Error::InvalidProposal);
+        
+        let voting_end = contract.get_proposal(proposal_id).unwrap().voting_end;
+        set_block_timestamp::<ink::env::DefaultEnvironment>(voting_end + 1);
+        assert_eq!(contract.update_proposal_status(proposal_id), Err(Error::InvalidProposal));
+        assert_eq!(contract.get_proposal(proposal_id).unwrap().status, ProposalStatus::Active);
+    }
+
+    #[ink::test]
+    fn stats_match_recount_across_transitions() {
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        contract.register_voter().unwrap();
+        let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
+        let ids: Vec<u32> = (0..60)
+            .map(|_| contract.create_proposal(title.clone(), description.clone(), proposal_type.clone(), governance_params.clone(), voting_options.clone(), vec![]).unwrap())
+            .collect();
+        
+        let recount = |contract: &TreasuryGovernance| {
+            let mut counts = StatusCounts::default();
+            for &id in &ids {
+                counts.increment(&contract.get_proposal(id).unwrap().status);
+            }
+            (contract.get_proposal_count(), counts.active, counts.executed, counts.passed, counts.rejected)
+        };
+        assert_eq!(contract.get_stats(), recount(&contract));
+        
+        // Every third proposal gets no vote and is rejected; a few are cancelled
+        for &id in ids.iter().filter(|&&id| id % 3 != 0) {
+            contract.vote(id, VoteChoice { option_index: 0, option_text: "Yes".to_string() }).unwrap();
+        }
+        for &id in ids.iter().filter(|&&id| id % 3 == 0).take(5) {
+            contract.cancel_proposal(id).unwrap();
+        }
+        assert_eq!(contract.get_stats(), recount(&contract));
+        
+        let voting_end = contract.get_proposal(ids[59]).unwrap().voting_end;
+        set_block_timestamp::<ink::env::DefaultEnvironment>(voting_end + 1);
+        for &id in &ids {
+            let _ = contract.update_proposal_status(id);
+        }
+        assert_eq!(contract.get_stats(), recount(&contract));
+        
+        let execution_time = contract.get_proposal(ids[0]).unwrap().execution_time;
+        set_block_timestamp::<ink::env::DefaultEnvironment>(execution_time + 1);
+        for &id in ids.iter().filter(|&&id| id % 3 == 1) {
+            contract.execute_proposal(id).unwrap();
+        }
+        let stats = contract.get_stats();
+        assert_eq!(stats, recount(&contract));
+        assert_eq!(stats, (60, 0, 20, 20, 15));
+        
+        // Counters lost by an older deployment are rebuilt range by range
+        contract.clear_status_counts_for_test();
+        set_caller(accounts.bob);
+        assert_eq!(contract.rebuild_stats(1, 61), Err(Error::NotAuthorized));
+        set_caller(accounts.alice);
+        let mut next = 1;
+        while next < 61 {
+            next = contract.rebuild_stats(next, 61).unwrap();
+        }
+        assert_eq!(contract.get_stats(), stats);
+    }
+
+    #[ink::test]
+    fn ownership_changes_hands_only_on_acceptance() {
+        use scale::Encode;
+        
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        let mut contract = TreasuryGovernance::new();
+        
+        set_caller(accounts.bob);
+        assert_eq!(contract.transfer_ownership(accounts.bob), Err(Error::NotAuthorized));
+        
+        // The nomination alone changes nothing
+        set_caller(accounts.alice);
+        contract.transfer_ownership(accounts.bob).unwrap();
+        assert_eq!(contract.get_pending_owner(), Some(accounts.bob));
+        assert_eq!(contract.get_owner(), accounts.alice);
+        set_caller(accounts.charlie);
+        assert_eq!(contract.accept_ownership(), Err(Error::NotAuthorized));
+        
+        set_caller(accounts.bob);
+        contract.accept_ownership().unwrap();
+        let event = ink::env::test::recorded_events().into_iter().last().unwrap();
+        assert_eq!(event.data, (accounts.alice, accounts.bob).encode());
+        assert_eq!(contract.get_owner(), accounts.bob);
+        assert_eq!(contract.get_pending_owner(), None);
+        assert_eq!(contract.accept_ownership(), Err(Error::NotAuthorized));
+        contract.set_paused(false).unwrap();
+        
+        set_caller(accounts.alice);
+        assert_eq!(contract.set_paused(false), Err(Error::NotAuthorized));
+    }
+
+    #[ink::test]
+    fn proposal_timing_in_seconds() {
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        contract.register_voter().unwrap();
+        let (title, description, proposal_type, mut governance_params, voting_options) = create_test_proposal_params();
+        let proposal_id = contract.create_proposal(title.clone(), description.clone(), proposal_type.clone(), governance_params.clone(), voting_options.clone(), vec![]).unwrap();
+        assert_eq!(contract.get_proposal_timing(proposal_id), Ok((604_800, 86_400)));
+        
+        // A custom execution delay supersedes the enum
+        governance_params.execution_delay_override = Some(36 * 60 * 60 * 1000);
+        let overridden = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
+        assert_eq!(contract.get_proposal_timing(overridden), Ok((7 * 24 * 60 * 60, 36 * 60 * 60)));
+        assert_eq!(contract.get_proposal_timing(99), Err(Error::ProposalNotFound));
+    }
+
+    #[ink::test]
+    fn default_grace_period_applies_to_new_proposals() {
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        contract.register_voter().unwrap();
+        let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
+        let before = contract.create_proposal(title.clone(), description.clone(), proposal_type.clone(), governance_params.clone(), voting_options.clone(), vec![]).unwrap();
+        
+        assert_eq!(contract.get_default_grace_period(), DEFAULT_GRACE_PERIOD);
+        assert_eq!(contract.set_default_grace_period(0), Err(Error::InvalidProposal));
+        contract.set_default_grace_period(MILLIS_PER_DAY).unwrap();
+        let after = contract.create_proposal(title.clone(), description.clone(), proposal_type.clone(), governance_params.clone(), voting_options.clone(), vec![]).unwrap();
+        let mut own_params = governance_params;
+        own_params.grace_period = Some(2 * MILLIS_PER_DAY);
+        let own = contract.create_proposal(title, description, proposal_type, own_params, voting_options, vec![]).unwrap();
+        assert_eq!(contract.get_proposal(before).unwrap().governance_params.grace_period, Some(DEFAULT_GRACE_PERIOD));
+        assert_eq!(contract.get_proposal(after).unwrap().governance_params.grace_period, Some(MILLIS_PER_DAY));
+        
+        for proposal_id in [before, after, own] {
+            contract.vote(proposal_id, VoteChoice { option_index: 0, option_text: "Yes".to_string() }).unwrap();
+        }
+        let proposal = contract.get_proposal(after).unwrap();
+        set_block_timestamp::<ink::env::DefaultEnvironment>(proposal.voting_end + 1);
+        for proposal_id in [before, after, own] {
+            contract.update_proposal_status(proposal_id).unwrap();
+        }
+        
+        // Just past the default window only the proposal that took the default expires
+        set_block_timestamp::<ink::env::DefaultEnvironment>(proposal.execution_time + MILLIS_PER_DAY + 1);
+        assert_eq!(contract.execute_proposal(after), Err(Error::ProposalExpired));
+        contract.expire_proposal(after).unwrap();
+        assert_eq!(contract.expire_proposal(own), Err(Error::ProposalNotReadyForExecution));
+        contract.execute_proposal(own).unwrap();
+        contract.execute_proposal(before).unwrap();
+    }
+
+    #[ink::test]
+    fn proposals_filtered_by_status() {
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        contract.register_voter().unwrap();
+        let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
+        let ids: Vec<u32> = (0..6)
+            .map(|_| contract.create_proposal(title.clone(), description.clone(), proposal_type.clone(), governance_params.clone(), voting_options.clone(), vec![]).unwrap())
+            .collect();
+        
+        // Two pass, one of which executes, two are rejected without votes and one is cancelled
+        contract.vote(ids[0], VoteChoice { option_index: 0, option_text: "Yes".to_string() }).unwrap();
+        contract.vote(ids[1], VoteChoice { option_index: 0, option_text: "Yes".to_string() }).unwrap();
+        contract.cancel_proposal(ids[2]).unwrap();
+        let voting_end = contract.get_proposal(ids[0]).unwrap().voting_end;
+        set_block_timestamp::<ink::env::DefaultEnvironment>(voting_end + 1);
+        for &id in &ids[..5] {
+            let _ = contract.update_proposal_status(id);
+        }
+        let execution_time = contract.get_proposal(ids[0]).unwrap().execution_time;
+        set_block_timestamp::<ink::env::DefaultEnvironment>(execution_time + 1);
+        contract.execute_proposal(ids[0]).unwrap();
+        
+        let ids_of = |(page, _): (Vec<Proposal>, u32)| page.into_iter().map(|proposal| proposal.id).collect::<Vec<u32>>();
+        assert_eq!(ids_of(contract.get_proposals_by_status(ProposalStatus::Executed, 1, 10)), vec![ids[0]]);
+        assert_eq!(ids_of(contract.get_proposals_by_status(ProposalStatus::Passed, 1, 10)), vec![ids[1]]);
+        assert_eq!(ids_of(contract.get_proposals_by_status(ProposalStatus::Cancelled, 1, 10)), vec![ids[2]]);
+        assert_eq!(ids_of(contract.get_proposals_by_status(ProposalStatus::Rejected, 1, 10)), vec![ids[3], ids[4]]);
+        assert_eq!(ids_of(contract.get_proposals_by_status(ProposalStatus::Active, 1, 10)), vec![ids[5]]);
+        
+        // Pages start at the given ID and stop at the limit
+        assert_eq!(ids_of(contract.get_proposals_by_status(ProposalStatus::Rejected, ids[4], 10)), vec![ids[4]]);
+        assert_eq!(ids_of(contract.get_proposals_by_status(ProposalStatus::Rejected, 1, 1)), vec![ids[3]]);
+        assert_eq!(contract.get_proposals_by_status(ProposalStatus::Rejected, 1, 1).1, ids[4]);
+        assert_eq!(contract.get_proposals_by_status(ProposalStatus::Rejected, 1, 10).1, ids[5] + 1);
+        
+        // The scan is bounded too: a match past one window of IDs needs another call from the cursor
+        let mut last = ids[5];
+        for _ in 0..MAX_PAGE_SIZE {
+            last = contract.create_proposal(title.clone(), description.clone(), proposal_type.clone(), governance_params.clone(), voting_options.clone(), vec![]).unwrap();
+        }
+        contract.cancel_proposal(last).unwrap();
+        let (page, next) = contract.get_proposals_by_status(ProposalStatus::Cancelled, ids[3], 10);
+        assert!(page.is_empty());
+        assert_eq!(next, ids[3] + MAX_PAGE_SIZE);
+        assert_eq!(ids_of(contract.get_proposals_by_status(ProposalStatus::Cancelled, next, 10)), vec![last]);
+    }
+
+    #[ink::test]
+    fn vote_text_must_match_option() {
+        use scale::Encode;
+        
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        contract.register_voter().unwrap();
+        let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
+        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
+        
+        // Text naming another option, or differing only in case, is rejected and nothing is counted
+        let result = contract.vote(proposal_id, VoteChoice { option_index: 0, option_text: "No".to_string() });
+        assert_eq!(result.unwrap_err(), crate::errors::Error::OptionMismatch);
+        let result = contract.vote(proposal_id, VoteChoice { option_index: 0, option_text: "yes".to_string() });
+        assert_eq!(result.unwrap_err(), crate::errors::Error::OptionMismatch);
+        assert!(contract.get_user_vote(proposal_id, accounts.alice).is_none());
+        assert_eq!(contract.get_proposal(proposal_id).unwrap().total_voters, 0);
+        
+        // Matching text is accepted and the event carries the stored label
+        contract.vote(proposal_id, VoteChoice { option_index: 0, option_text: "Yes".to_string() }).unwrap();
+        let event = ink::env::test::recorded_events().into_iter().last().unwrap();
+        assert_eq!(event.data, (proposal_id, accounts.alice, 0u32, "Yes".to_string(), 1u128).encode());
+        assert_eq!(contract.get_user_vote(proposal_id, accounts.alice).unwrap().choice.option_text, "Yes");
+        
+        // Changing a vote is held to the same rule
+        let result = contract.change_vote(proposal_id, VoteChoice { option_index: 1, option_text: "Yes".to_string() });
+        assert_eq!(result.unwrap_err(), crate::errors::Error::OptionMismatch);
+        contract.change_vote(proposal_id, VoteChoice { option_index: 1, option_text: "No".to_string() }).unwrap();
+    }
+
+    #[ink::test]
+    fn quorum_timing_chooses_denominator() {
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        contract.register_voter().unwrap();
+        assert_eq!(contract.get_quorum_timing(), QuorumTiming::AtCreation);
+        
+        // Two proposals opened while Alice is the only voter, each carrying her vote
+        let (title, description, proposal_type, mut governance_params, voting_options) = create_test_proposal_params();
+        governance_params.quorum_threshold = QuorumThreshold::TwentyFive;
+        let first = contract.create_proposal(title.clone(), description.clone(), proposal_type.clone(), governance_params.clone(), voting_options.clone(), vec![]).unwrap();
+        let second = contract.create_proposal(title.clone(), description.clone(), proposal_type.clone(), governance_params.clone(), voting_options.clone(), vec![]).unwrap();
+        contract.vote(first, VoteChoice { option_index: 0, option_text: "Yes".to_string() }).unwrap();
+        contract.vote(second, VoteChoice { option_index: 0, option_text: "Yes".to_string() }).unwrap();
+        
+        // Five voters join before voting ends
+        for voter in [accounts.bob, accounts.charlie, accounts.django, accounts.eve, accounts.frank] {
+            set_caller(voter);
+            contract.register_voter().unwrap();
+        }
+        let voting_end = contract.get_proposal(first).unwrap().voting_end;
+        set_block_timestamp::<ink::env::DefaultEnvironment>(voting_end + 1);
+        
+        // At creation 25% of one voter is Alice's vote; at finalization 25% of six needs two
+        assert_eq!(contract.update_proposal_status(first), Ok(ProposalStatus::Passed));
+        set_caller(accounts.alice);
+        contract.set_quorum_timing(QuorumTiming::AtFinalization).unwrap();
+        assert_eq!(contract.update_proposal_status(second), Ok(ProposalStatus::Rejected));
+        
+        // With the electorate shrinking the outcome flips: six voters at creation, one at finalization
+        let third = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
+        contract.vote(third, VoteChoice { option_index: 0, option_text: "Yes".to_string() }).unwrap();
+        for voter in [accounts.bob, accounts.charlie, accounts.django, accounts.eve, accounts.frank] {
+            set_caller(voter);
+            contract.deregister_voter().unwrap();
+        }
+        assert!(contract.has_reached_quorum(third).unwrap());
+        set_caller(accounts.alice);
+        contract.set_quorum_timing(QuorumTiming::AtCreation).unwrap();
+        assert!(!contract.has_reached_quorum(third).unwrap());
+        
+        // Only the owner may change the timing
+        set_caller(accounts.bob);
+        assert_eq!(contract.set_quorum_timing(QuorumTiming::AtFinalization).unwrap_err(), crate::errors::Error::NotAuthorized);
+    }
+
+    #[ink::test]
+    fn stake_stays_locked_while_its_ballot_is_open() {
+        use ink::primitives::U256;
+        
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        let mut contract = TreasuryGovernance::new();
+        contract.set_weight_source(WeightSource::Stake).unwrap();
+        let contract_account = ink::env::test::callee::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_account_balance(contract_account, U256::from(1_000u128));
+        
+        set_caller(accounts.bob);
+        ink::env::test::set_value_transferred(U256::from(500u128));
+        contract.register_voter().unwrap();
+        ink::env::test::set_value_transferred(U256::zero());
+        
+        set_caller(accounts.alice);
+        let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
+        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
+        set_caller(accounts.bob);
+        contract.vote(proposal_id, VoteChoice { option_index: 0, option_text: "Yes".to_string() }).unwrap();
+        
+        // Bob cannot take the stake back to re-stake it from a fresh account while his ballot counts
+        assert_eq!(contract.deregister_voter(), Err(Error::StakeLocked));
+        assert!(contract.is_voter_registered(accounts.bob));
+        assert_eq!(contract.get_stake(accounts.bob), 500);
+        assert_eq!(contract.get_total_weight(), 500);
+        
+        // Once the proposal is finalized the stake is released
+        let voting_end = contract.get_proposal(proposal_id).unwrap().voting_end;
+        set_block_timestamp::<ink::env::DefaultEnvironment>(voting_end + 1);
+        contract.update_proposal_status(proposal_id).unwrap();
+        contract.deregister_voter().unwrap();
+        assert_eq!(contract.get_stake(accounts.bob), 0);
+        assert_eq!(contract.get_total_weight(), 0);
+    }
+
+    #[ink::test]
+    fn slashing_floor_does_not_arm_empty_stakes() {
+        use ink::primitives::U256;
+        
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        let mut contract = TreasuryGovernance::new();
+        contract.set_weight_source(WeightSource::Stake).unwrap();
+        contract.set_absence_slashing(Some(AbsenceSlashing { miss_threshold: 1, retained_percent: 50 })).unwrap();
+        ink::env::test::set_value_transferred(U256::from(100u128));
+        contract.register_voter().unwrap();
+        ink::env::test::set_value_transferred(U256::zero());
+        set_caller(accounts.bob);
+        contract.register_voter().unwrap();
+        
+        // Bob misses a proposal, which would floor a slashed weight at one vote
+        set_caller(accounts.alice);
+        let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
+        let missed = contract.create_proposal(title.clone(), description.clone(), proposal_type.clone(), governance_params.clone(), voting_options.clone(), vec![]).unwrap();
+        contract.vote(missed, VoteChoice { option_index: 0, option_text: "Yes".to_string() }).unwrap();
+        let voting_end = contract.get_proposal(missed).unwrap().voting_end;
+        set_block_timestamp::<ink::env::DefaultEnvironment>(voting_end + 1);
+        contract.update_proposal_status(missed).unwrap();
+        assert_eq!(contract.get_missed_proposals(accounts.bob), 1);
+        
+        // Without a stake Bob still carries no weight and cannot vote
+        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
+        set_caller(accounts.bob);
+        assert_eq!(contract.get_voting_power(accounts.bob), Ok(0));
+        assert_eq!(contract.vote(proposal_id, VoteChoice { option_index: 0, option_text: "Yes".to_string() }), Err(Error::InsufficientVotingPower));
+    }
+
+    #[ink::test]
+    fn vote_reward_never_draws_on_stakes() {
+        use ink::primitives::U256;
+        
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        let mut contract = TreasuryGovernance::new();
+        contract.set_weight_source(WeightSource::Stake).unwrap();
+        contract.set_vote_reward(10, 100).unwrap();
+        
+        // The contract holds only Alice's stake, so the treasury itself is empty
+        let contract_account = ink::env::test::callee::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_account_balance(contract_account, U256::from(100u128));
+        ink::env::test::set_value_transferred(U256::from(100u128));
+        contract.register_voter().unwrap();
+        ink::env::test::set_value_transferred(U256::zero());
+        assert_eq!(contract.get_treasury_balance(), 0);
+        
+        // The reward is skipped and the vote still goes through
+        let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
+        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
+        contract.vote(proposal_id, VoteChoice { option_index: 0, option_text: "Yes".to_string() }).unwrap();
+        assert_eq!(contract.get_rewards_distributed(proposal_id), 0);
+        assert_eq!(contract.get_proposal(proposal_id).unwrap().vote_counts, vec![100, 0]);
+    }
+
+    #[ink::test]
+    fn tally_audit_is_guarded_and_reset_with_votes() {
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        for voter in [accounts.alice, accounts.bob] {
+            set_caller(voter);
+            contract.register_voter().unwrap();
+        }
+        set_caller(accounts.alice);
+        let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
+        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
+        contract.vote(proposal_id, VoteChoice { option_index: 0, option_text: "Yes".to_string() }).unwrap();
+        
+        // Others cannot restart the guardian's audit
+        let report = contract.audit_tally(proposal_id, 0, 10).unwrap();
+        assert!(report.complete);
+        set_caller(accounts.bob);
+        assert_eq!(contract.audit_tally(proposal_id, 0, 10).unwrap_err(), crate::errors::Error::NotAuthorized);
+        
+        // After a reset the old recount cannot be written over the same number of new ballots
+        set_caller(accounts.alice);
+        contract.reset_votes(proposal_id).unwrap();
+        set_caller(accounts.bob);
+        contract.vote(proposal_id, VoteChoice { option_index: 1, option_text: "No".to_string() }).unwrap();
+        set_caller(accounts.alice);
+        assert_eq!(contract.repair_tally(proposal_id).unwrap_err(), crate::errors::Error::InvalidProposal);
+        assert_eq!(contract.get_proposal(proposal_id).unwrap().vote_counts, vec![0, 1]);
+    }
+
+    #[ink::test]
+    fn absences_only_tracked_while_slashing_enabled() {
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        for voter in [accounts.alice, accounts.bob] {
+            set_caller(voter);
+            contract.register_voter().unwrap();
+        }
+        
+        // Proposals finalized with slashing off leave no absence record
+        set_caller(accounts.alice);
+        let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
+        let first = contract.create_proposal(title.clone(), description.clone(), proposal_type.clone(), governance_params.clone(), voting_options.clone(), vec![]).unwrap();
+        contract.vote(first, VoteChoice { option_index: 0, option_text: "Yes".to_string() }).unwrap();
+        let voting_end = contract.get_proposal(first).unwrap().voting_end;
+        set_block_timestamp::<ink::env::DefaultEnvironment>(voting_end + 1);
+        contract.update_proposal_status(first).unwrap();
+        assert_eq!(contract.get_missed_proposals(accounts.bob), 0);
+        
+        // Once enabled, misses count from then on
+        contract.set_absence_slashing(Some(AbsenceSlashing { miss_threshold: 1, retained_percent: 50 })).unwrap();
+        let second = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
+        contract.vote(second, VoteChoice { option_index: 0, option_text: "Yes".to_string() }).unwrap();
+        let voting_end = contract.get_proposal(second).unwrap().voting_end;
+        set_block_timestamp::<ink::env::DefaultEnvironment>(voting_end + 1);
+        contract.update_proposal_status(second).unwrap();
+        assert_eq!(contract.get_missed_proposals(accounts.alice), 0);
+        assert_eq!(contract.get_missed_proposals(accounts.bob), 1);
+    }
+
+    #[ink::test]
+    fn execution_uses_winner_fixed_at_finalization() {
+        use ink::primitives::U256;
+        
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        let contract_account = ink::env::test::callee::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_account_balance(contract_account, U256::from(100_000u128));
+        for voter in [accounts.alice, accounts.bob] {
+            set_caller(voter);
+            contract.register_voter().unwrap();
+        }
+        
+        set_caller(accounts.alice);
+        contract.set_tie_break_policy(TieBreakPolicy::LowestIndex).unwrap();
+        let (title, description, proposal_type, governance_params, _) = create_test_proposal_params();
+        let voting_options = VotingOptions {
+            options: vec!["Fund Bob".to_string(), "Fund Charlie".to_string()],
+            payloads: vec![],
+            actions: vec![
+                OutcomeAction::Transfer { recipient: accounts.bob, amount: 5_000 },
+                OutcomeAction::Transfer { recipient: accounts.charlie, amount: 5_000 },
+            ],
+            abstain_index: None,
+        };
+        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
+        contract.vote(proposal_id, VoteChoice { option_index: 0, option_text: "Fund Bob".to_string() }).unwrap();
+        set_caller(accounts.bob);
+        contract.vote(proposal_id, VoteChoice { option_index: 1, option_text: "Fund Charlie".to_string() }).unwrap();
+        
+        // The tie resolves to the first option when voting concludes
+        let proposal = contract.get_proposal(proposal_id).unwrap();
+        set_block_timestamp::<ink::env::DefaultEnvironment>(proposal.voting_end + 1);
+        assert_eq!(contract.update_proposal_status(proposal_id).unwrap(), ProposalStatus::Passed);
+        assert_eq!(contract.get_proposal(proposal_id).unwrap().winning_option, Some(0));
+        
+        // Changing the policy afterwards does not change what executes
+        set_caller(accounts.alice);
+        contract.set_tie_break_policy(TieBreakPolicy::Reject).unwrap();
+        set_block_timestamp::<ink::env::DefaultEnvironment>(proposal.execution_time);
+        let balance_of = |account| ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(account).unwrap();
+        let (bob_before, charlie_before) = (balance_of(accounts.bob), balance_of(accounts.charlie));
+        contract.execute_proposal(proposal_id).unwrap();
+        assert_eq!(balance_of(accounts.bob), bob_before + U256::from(5_000u128));
+        assert_eq!(balance_of(accounts.charlie), charlie_before);
+    }
+
+    #[ink::test]
+    fn reset_votes_clears_decision_state_and_vote_counts() {
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        contract.set_confirmation_period(MILLIS_PER_DAY).unwrap();
+        contract.register_voter().unwrap();
+        
+        let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
+        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
+        set_block_timestamp::<ink::env::DefaultEnvironment>(100);
+        contract.vote(proposal_id, VoteChoice { option_index: 0, option_text: "Yes".to_string() }).unwrap();
+        assert_eq!(contract.get_decision_state(proposal_id).unwrap(), DecisionState::Confirming { since: 100 });
+        assert_eq!(contract.get_voter_info(accounts.alice).unwrap().votes_cast, 1);
+        
+        // The reset stops the confirmation clock and takes back the ballot from the voter's record
+        contract.reset_votes(proposal_id).unwrap();
+        assert_eq!(contract.get_decision_state(proposal_id).unwrap(), DecisionState::Deciding);
+        assert_eq!(contract.get_voter_info(accounts.alice).unwrap().votes_cast, 0);
+        
+        // Voting again starts a fresh clock
+        set_block_timestamp::<ink::env::DefaultEnvironment>(300);
+        contract.vote(proposal_id, VoteChoice { option_index: 0, option_text: "Yes".to_string() }).unwrap();
+        assert_eq!(contract.get_decision_state(proposal_id).unwrap(), DecisionState::Confirming { since: 300 });
+        assert_eq!(contract.get_voter_info(accounts.alice).unwrap().votes_cast, 1);
+    }
+
+    #[ink::test]
+    fn unexecuted_proposals_can_always_be_pruned() {
+        let accounts = default_accounts();
+        set_caller(accounts.alice);
+        
+        let mut contract = TreasuryGovernance::new();
+        contract.register_voter().unwrap();
+        let (title, description, proposal_type, governance_params, voting_options) = create_test_proposal_params();
+        let proposal_id = contract.create_proposal(title, description, proposal_type, governance_params, voting_options, vec![]).unwrap();
+        contract.vote(proposal_id, VoteChoice { option_index: 0, option_text: "Yes".to_string() }).unwrap();
+        
+        let proposal = contract.get_proposal(proposal_id).unwrap();
+        set_block_timestamp::<ink::env::DefaultEnvironment>(proposal.voting_end + 1);
+        contract.update_proposal_status(proposal_id).unwrap();
+        assert!(contract.get_dashboard().open_proposal_ids.contains(&proposal_id));
+        
+        // A passed proposal nobody executes leaves the open index once the default window ends
+        set_block_timestamp::<ink::env::DefaultEnvironment>(proposal.execution_time + DEFAULT_GRACE_PERIOD + 1);
+        contract.expire_proposal(proposal_id).unwrap();
+        assert!(!contract.get_dashboard().open_proposal_ids.contains(&proposal_id));
+    }
+
 }
\ No newline at end of file
@@ -4,7 +4,81 @@ use crate::types::*;
 use ink::storage::Mapping;
 use ink::prelude::string::String;
 use ink::prelude::vec::Vec;
-use ink::primitives::H160;
+use ink::primitives::{H160, U256};
+
+/// Raw SCALE-encoded call arguments, written to the input buffer as-is
+struct CallInput<'a>(&'a [u8]);
+
+impl scale::Encode for CallInput<'_> {
+    fn encode_to<T: scale::Output + ?Sized>(&self, dest: &mut T) {
+        dest.write(self.0);
+    }
+}
+
+/// Test double for contract-account detection, since the off-chain environment deploys no code
+#[cfg(test)]
+pub mod contract_accounts {
+    use ink::primitives::H160;
+    use std::cell::RefCell;
+
+    std::thread_local! {
+        static CONTRACTS: RefCell<Vec<H160>> = RefCell::new(Vec::new());
+    }
+
+    /// Treat an account as a contract for the rest of the current test
+    pub fn mark_as_contract(account: H160) {
+        CONTRACTS.with(|contracts| contracts.borrow_mut().push(account));
+    }
+
+    pub fn is_contract(account: H160) -> bool {
+        CONTRACTS.with(|contracts| contracts.borrow().contains(&account))
+    }
+}
+
+/// Test double for oracle contracts, since the off-chain environment cannot cross-call
+#[cfg(test)]
+pub mod oracle_responses {
+    use ink::primitives::H160;
+    use std::cell::RefCell;
+
+    std::thread_local! {
+        static RESPONSES: RefCell<Vec<(H160, bool)>> = RefCell::new(Vec::new());
+    }
+
+    /// Make an oracle answer `holds` for the rest of the current test
+    pub fn set_response(oracle: H160, holds: bool) {
+        RESPONSES.with(|responses| {
+            let mut responses = responses.borrow_mut();
+            responses.retain(|(account, _)| *account != oracle);
+            responses.push((oracle, holds));
+        });
+    }
+
+    pub fn response(oracle: H160) -> Option<bool> {
+        RESPONSES.with(|responses| {
+            responses.borrow().iter().find(|(account, _)| *account == oracle).map(|(_, holds)| *holds)
+        })
+    }
+}
+
+/// Record of the lifecycle hooks run during a test, in the order they ran
+#[cfg(test)]
+pub mod hook_trace {
+    use std::cell::RefCell;
+
+    std::thread_local! {
+        static TRACE: RefCell<Vec<&'static str>> = RefCell::new(Vec::new());
+    }
+
+    pub fn record(hook: &'static str) {
+        TRACE.with(|trace| trace.borrow_mut().push(hook));
+    }
+
+    /// Take the hooks recorded so far, clearing the trace
+    pub fn take() -> Vec<&'static str> {
+        TRACE.with(|trace| trace.take())
+    }
+}
 
 #[ink::contract]
 pub mod treasury_governance {
@@ -18,6 +92,7 @@ pub mod treasury_governance {
         #[ink(topic)]
         proposer: H160,
         title: String,
+        co_authors: Vec<H160>,
     }
 
     #[ink(event)]
@@ -31,11 +106,177 @@ pub mod treasury_governance {
         weight: u128,
     }
 
+    #[ink(event)]
+    pub struct VoteChanged {
+        #[ink(topic)]
+        proposal_id: u32,
+        #[ink(topic)]
+        voter: H160,
+        old_index: u32,
+        new_index: u32,
+    }
+
     #[ink(event)]
     pub struct ProposalExecuted {
         #[ink(topic)]
         proposal_id: u32,
         status: ProposalStatus,
+        executor: Option<H160>,
+        ballot_commitment: [u8; 32],
+    }
+
+    #[ink(event)]
+    pub struct VotesReset {
+        #[ink(topic)]
+        proposal_id: u32,
+        cleared_votes: u32,
+    }
+
+    #[ink(event)]
+    pub struct BatchFinalized {
+        count: u32,
+        passed: u32,
+        rejected: u32,
+    }
+
+    #[ink(event)]
+    pub struct ProposalSuspended {
+        #[ink(topic)]
+        proposal_id: u32,
+        suspended_by: H160,
+    }
+
+    #[ink(event)]
+    pub struct ProposalCancelled {
+        #[ink(topic)]
+        proposal_id: u32,
+        cancelled_by: H160,
+    }
+
+    #[ink(event)]
+    pub struct ProposalExpired {
+        #[ink(topic)]
+        proposal_id: u32,
+    }
+
+    #[ink(event)]
+    pub struct ProposalFlagged {
+        #[ink(topic)]
+        proposal_id: u32,
+        flagger: H160,
+    }
+
+    #[ink(event)]
+    pub struct ProposalAutoSuspended {
+        #[ink(topic)]
+        proposal_id: u32,
+        flags: u32,
+    }
+
+    #[ink(event)]
+    pub struct ProposalResumed {
+        #[ink(topic)]
+        proposal_id: u32,
+        extended_by: Timestamp,
+    }
+
+    #[ink(event)]
+    pub struct EnteredConfirming {
+        #[ink(topic)]
+        proposal_id: u32,
+        since: Timestamp,
+    }
+
+    #[ink(event)]
+    pub struct LeftConfirming {
+        #[ink(topic)]
+        proposal_id: u32,
+    }
+
+    #[ink(event)]
+    pub struct GovernanceActionApplied {
+        #[ink(topic)]
+        proposal_id: u32,
+        action: GovernanceAction,
+    }
+
+    #[ink(event)]
+    pub struct FundsDeposited {
+        #[ink(topic)]
+        from: H160,
+        amount: u128,
+    }
+
+    #[ink(event)]
+    pub struct ParameterSunset {
+        action: GovernanceAction,
+    }
+
+    #[ink(event)]
+    pub struct OwnershipTransferred {
+        #[ink(topic)]
+        old: H160,
+        #[ink(topic)]
+        new: H160,
+    }
+
+    #[ink(event)]
+    pub struct VoterRegistered {
+        #[ink(topic)]
+        voter: H160,
+    }
+
+    #[ink(event)]
+    pub struct VoterDeregistered {
+        #[ink(topic)]
+        voter: H160,
+    }
+
+    #[ink(event)]
+    pub struct DelegateChanged {
+        #[ink(topic)]
+        delegator: H160,
+        delegate: Option<H160>,
+    }
+
+    #[ink(event)]
+    pub struct ProposalEndorsed {
+        #[ink(topic)]
+        proposal_id: u32,
+        #[ink(topic)]
+        endorser: H160,
+    }
+
+    #[ink(event)]
+    pub struct EndorsementWithdrawn {
+        #[ink(topic)]
+        proposal_id: u32,
+        #[ink(topic)]
+        endorser: H160,
+    }
+
+    #[ink(event)]
+    pub struct ProposalRevised {
+        #[ink(topic)]
+        proposal_id: u32,
+        #[ink(topic)]
+        revision_id: u32,
+    }
+
+    #[ink(event)]
+    pub struct TallyDiscrepancy {
+        #[ink(topic)]
+        proposal_id: u32,
+        stored: Vec<u128>,
+        recomputed: Vec<u128>,
+    }
+
+    #[ink(event)]
+    pub struct TallyRepaired {
+        #[ink(topic)]
+        proposal_id: u32,
+        previous: Vec<u128>,
+        repaired: Vec<u128>,
     }
 
     /// Defines the storage of your contract.
@@ -43,18 +284,144 @@ pub mod treasury_governance {
     pub struct TreasuryGovernance {
         /// Mapping from proposal ID to Proposal
         proposals: Mapping<u32, Proposal>,
-        /// Mapping from (proposal_id, voter) to Vote
-        votes: Mapping<(u32, H160), Vote>,
-        /// Mapping from voter address to registration status
+        /// Mapping from (proposal_id, voter) to their compact ballot
+        votes: Mapping<(u32, H160), StoredVote>,
+        /// Mapping from (proposal_id, index) to voter, for iterating a proposal's votes
+        proposal_voters: Mapping<(u32, u32), H160>,
+        /// Mapping from voter address to their registration record
+        voter_info: Mapping<H160, VoterInfo>,
+        /// Legacy registration flags written before `voter_info` existed, upgraded lazily
         registered_voters: Mapping<H160, bool>,
+        /// Legacy registration timestamps written before `voter_info` existed, upgraded lazily
+        voter_registered_at: Mapping<H160, Timestamp>,
+        /// Mapping from delegator to the account voting on their behalf
+        delegations: Mapping<H160, H160>,
+        /// Mapping from delegate to the number of accounts delegating to them
+        delegated_counts: Mapping<H160, u32>,
+        /// Mapping from (delegate, index) to delegator, for enumeration
+        delegators: Mapping<(H160, u32), H160>,
+        /// Mapping from delegator to their position in `delegators`
+        delegator_positions: Mapping<H160, u32>,
+        /// Mapping from (proposal_id, delegator) to the delegate whose ballot carried their weight
+        delegated_ballots: Mapping<(u32, H160), H160>,
+        /// Mapping from (proposal_id, index) to endorser, for enumeration
+        endorsers: Mapping<(u32, u32), H160>,
+        /// Mapping from (proposal_id, endorser) to their position in `endorsers`
+        endorser_positions: Mapping<(u32, H160), u32>,
+        /// Mapping from (proposal_id, index) to flagger, for enumeration
+        flaggers: Mapping<(u32, u32), H160>,
+        /// Mapping from (proposal_id, flagger) to whether they flagged the proposal
+        has_flagged: Mapping<(u32, H160), bool>,
+        /// Mapping from proposal_id to the number of flags it received
+        flag_counts: Mapping<u32, u32>,
+        /// Accounts barred from flagging by governance
+        barred_flaggers: Mapping<H160, bool>,
+        /// Mapping from account to the proposals it is watching
+        watchlists: Mapping<H160, Vec<u32>>,
+        /// Mapping from proposal type to its allowed duration range
+        duration_bounds: Mapping<ProposalType, DurationBounds>,
+        /// Mapping from proposal type to its minimum title and description lengths
+        length_minimums: Mapping<ProposalType, LengthMinimums>,
+        /// Decision sub-state of active proposals while a confirmation period is configured
+        decision_states: Mapping<u32, DecisionState>,
+        /// Reputation points earned from governance history
+        reputation: Mapping<H160, u128>,
+        /// Base voting weight assigned to a voter by the owner (1 when unset)
+        assigned_weights: Mapping<H160, u128>,
+        /// Native tokens each voter has staked
+        stakes: Mapping<H160, u128>,
+        /// Running hash commitment over each proposal's ballots
+        ballot_commitments: Mapping<u32, BallotCommitment>,
+        /// Eligibility root each proposal was pinned to at creation, if any
+        proposal_eligibility: Mapping<u32, EligibilityRoot>,
+        /// Vote rewards paid out so far per proposal
+        rewards_distributed: Mapping<u32, u128>,
+        /// Finalized proposal count at each voter's latest participation or registration
+        participation_marks: Mapping<H160, u32>,
+        /// Progress of tally audits, rebuilt from raw vote records in chunks
+        tally_audits: Mapping<u32, TallyAudit>,
+        /// Revision that superseded each revised proposal
+        revised_as: Mapping<u32, u32>,
+        /// IDs of proposals that have not reached a terminal status
+        open_proposals: Vec<u32>,
+        /// IDs of the most recently created proposals, oldest first
+        recent_proposals: Vec<u32>,
+        /// Number of proposals currently in each status
+        status_counts: StatusCounts,
         /// Next proposal ID
         next_proposal_id: u32,
         /// Total number of proposals
         proposal_count: u32,
         /// Total number of voters(for quorum calculation)
         total_voters: u32,
+        /// Sum of all voter stakes, held apart from the treasury
+        total_weight: u128,
         /// contract owner
         owner: H160,
+        /// Account nominated to become owner once it accepts
+        pending_owner: Option<H160>,
+        /// Account allowed to suspend and resume individual proposals
+        guardian: H160,
+        /// Whether creation, voting and execution are paused
+        paused: bool,
+        /// Maximum number of proposals open for voting at once (0 means unlimited)
+        max_active_proposals: u32,
+        /// Maximum number of registered voters (0 means unlimited)
+        max_voters: u32,
+        /// Extra voting weight granted per full day of membership (0 disables tenure weighting)
+        tenure_weight_per_day: u128,
+        /// Minimum number of distinct voters required for a proposal to pass
+        min_distinct_participants: u32,
+        /// Milliseconds a newly registered voter must wait before voting
+        voting_probation: Timestamp,
+        /// Whether voters still in probation count toward the quorum denominator
+        probation_counts_toward_quorum: bool,
+        /// Registration timestamps still inside the probation window, oldest first
+        recent_registrations: Vec<Timestamp>,
+        /// Lowest quorum threshold new proposals may use, adjustable through governance
+        default_quorum_threshold: QuorumThreshold,
+        /// Block timestamps beyond this are treated as clock faults and block proposal creation
+        max_reasonable_timestamp: Timestamp,
+        /// Milliseconds a proposal must keep meeting its thresholds before it passes (0 disables)
+        confirmation_period: Timestamp,
+        /// Grace period given to new proposals that do not set their own, in milliseconds
+        default_grace_period: Timestamp,
+        /// Whether contract accounts may register themselves as voters
+        allow_contract_voters: bool,
+        /// Where voting weight is derived from
+        weight_source: WeightSource,
+        /// Reputation accrual rates and weight conversion
+        reputation_config: ReputationConfig,
+        /// Minimum milliseconds between proposal creation and the voting deadline
+        min_voting_window: Timestamp,
+        /// Merkle root of eligible addresses pinned by new proposals (None uses registration)
+        eligibility: Option<EligibilityRoot>,
+        /// Treasury payout to each voter per ballot (0 disables rewards)
+        vote_reward: u128,
+        /// Maximum total vote rewards paid out per proposal
+        vote_reward_budget: u128,
+        /// Number of proposals finalized so far, used to count missed proposals
+        finalized_proposals: u32,
+        /// Weight reduction for voters who keep missing proposals (None disables)
+        absence_slashing: Option<AbsenceSlashing>,
+        /// Halt on new proposals voted in by governance, until it expires or is resumed
+        governance_halt: Option<GovernanceHalt>,
+        /// Voting weight a proposer must hold to create a proposal (0 disables the check)
+        min_weight_to_propose: u128,
+        /// Most voting weight a single ballot may carry (0 means uncapped)
+        max_effective_power: u128,
+        /// Whether treasury proposals must carry at least one transfer action
+        require_treasury_actions: bool,
+        /// Previous values of experimental parameter changes, at most one per parameter
+        pending_sunsets: Vec<PendingSunset>,
+        /// Flags that automatically suspend a proposal (None disables auto-suspension)
+        flag_threshold: Option<FlagThreshold>,
+        /// How ties between the leading options are resolved
+        tie_break_policy: TieBreakPolicy,
+        /// When the quorum denominator of registration-based proposals is counted
+        quorum_timing: QuorumTiming,
+        /// Mapping from (proposal_id, option_index) to the time of the option's latest vote
+        option_last_vote: Mapping<(u32, u32), Timestamp>,
     }
 
     impl TreasuryGovernance {
@@ -65,60 +432,145 @@ pub mod treasury_governance {
             Self {
                 proposals: Mapping::new(),
                 votes: Mapping::new(),
+                proposal_voters: Mapping::new(),
+                voter_info: Mapping::new(),
                 registered_voters: Mapping::new(),
+                voter_registered_at: Mapping::new(),
+                delegations: Mapping::new(),
+                delegated_counts: Mapping::new(),
+                delegators: Mapping::new(),
+                delegator_positions: Mapping::new(),
+                delegated_ballots: Mapping::new(),
+                endorsers: Mapping::new(),
+                endorser_positions: Mapping::new(),
+                flaggers: Mapping::new(),
+                has_flagged: Mapping::new(),
+                flag_counts: Mapping::new(),
+                barred_flaggers: Mapping::new(),
+                watchlists: Mapping::new(),
+                duration_bounds: Mapping::new(),
+                length_minimums: Mapping::new(),
+                decision_states: Mapping::new(),
+                reputation: Mapping::new(),
+                assigned_weights: Mapping::new(),
+                stakes: Mapping::new(),
+                ballot_commitments: Mapping::new(),
+                proposal_eligibility: Mapping::new(),
+                rewards_distributed: Mapping::new(),
+                participation_marks: Mapping::new(),
+                tally_audits: Mapping::new(),
+                revised_as: Mapping::new(),
+                open_proposals: Vec::new(),
+                recent_proposals: Vec::new(),
+                status_counts: StatusCounts::default(),
                 next_proposal_id: 1,
                 proposal_count: 0,
                 total_voters: 0,
+                total_weight: 0,
                 owner: caller,
+                pending_owner: None,
+                guardian: caller,
+                paused: false,
+                max_active_proposals: 0,
+                max_voters: 0,
+                tenure_weight_per_day: 0,
+                min_distinct_participants: 0,
+                voting_probation: 0,
+                probation_counts_toward_quorum: true,
+                recent_registrations: Vec::new(),
+                default_quorum_threshold: QuorumThreshold::Ten,
+                max_reasonable_timestamp: Timestamp::MAX,
+                confirmation_period: 0,
+                default_grace_period: DEFAULT_GRACE_PERIOD,
+                allow_contract_voters: true,
+                weight_source: WeightSource::Tenure,
+                reputation_config: ReputationConfig::default(),
+                min_voting_window: DEFAULT_MIN_VOTING_WINDOW,
+                eligibility: None,
+                vote_reward: 0,
+                vote_reward_budget: 0,
+                finalized_proposals: 0,
+                absence_slashing: None,
+                governance_halt: None,
+                min_weight_to_propose: 0,
+                max_effective_power: 0,
+                require_treasury_actions: false,
+                pending_sunsets: Vec::new(),
+                flag_threshold: None,
+                tie_break_policy: TieBreakPolicy::Reject,
+                quorum_timing: QuorumTiming::AtCreation,
+                option_last_vote: Mapping::new(),
             }
         }
 
-        /// Create a new proposal
-        #[ink(message)]
-        pub fn create_proposal(&mut self, title: String, description: String, proposal_type: ProposalType, governance_params: GovernanceParameters, voting_options: VotingOptions) -> Result<u32> {
-            // Validate voting options
-            if voting_options.options.is_empty() || voting_options.options.len() > 10 {
-                return Err(Error::InvalidProposal);
+        /// Constructor that also seeds the treasury with the transferred value
+        #[ink(constructor, payable)]
+        pub fn new_funded() -> Self {
+            let contract = Self::new();
+            let amount: u128 = Self::env().transferred_value().try_into().unwrap_or(u128::MAX);
+            if amount > 0 {
+                Self::env().emit_event(FundsDeposited {
+                    from: Self::env().caller(),
+                    amount,
+                });
             }
-           
-            // Validate that all voting options are non-empty strings
-            for option in &voting_options.options {
-                if option.trim().is_empty() {
-                    return Err(Error::InvalidProposal);
-                }
+            contract
+        }
+
+        /// Send native tokens into the treasury
+        #[ink(message, payable)]
+        pub fn deposit_funds(&mut self) -> Result<()> {
+            let amount: u128 = self.env().transferred_value().try_into().map_err(|_| Error::ArithmeticOverflow)?;
+            if amount == 0 {
+                return Err(Error::InvalidAmount);
             }
             
-            let proposal_id = self.next_proposal_id;
-            let caller = self.env().caller();
+            self.env().emit_event(FundsDeposited {
+                from: self.env().caller(),
+                amount,
+            });
             
-            // Calculate voting end time based on governance parameters
-            let current_time = self.env().block_timestamp() as u32;
-            let voting_duration = match governance_params.voting_period {
-                VotingPeriod::ThreeDays => 3 * 24 * 60 * 60, // 3 days in seconds
-                VotingPeriod::SevenDays => 7 * 24 * 60 * 60,
-                VotingPeriod::FourteenDays => 14 * 24 * 60 * 60,
-                VotingPeriod::ThirtyDays => 30 * 24 * 60 * 60,
-            };
+            Ok(())
+        }
+
+        /// Get the native balance available to the treasury, excluding voter stakes
+        #[ink(message)]
+        pub fn get_treasury_balance(&self) -> u128 {
+            self.contract_balance().saturating_sub(self.total_weight)
+        }
+
+        /// Create a new proposal
+        #[ink(message)]
+        pub fn create_proposal(&mut self, title: String, description: String, proposal_type: ProposalType, mut governance_params: GovernanceParameters, voting_options: VotingOptions, co_authors: Vec<H160>) -> Result<u32> {
+            let current_time = self.current_time();
+            let caller = self.env().caller();
+            self.check_can_create(caller, current_time)?;
+            let (voting_end, execution_time) = self.validate_proposal(&title, &description, &proposal_type, &voting_options, &governance_params, current_time)?;
+            let co_authors = self.validate_co_authors(caller, co_authors)?;
             
-            // Calculate execution time based on execution delay
-            let execution_delay = match governance_params.execution_delay {
-                ExecutionDelay::Immediately => 0,
-                ExecutionDelay::OneDay => 24 * 60 * 60,
-                ExecutionDelay::TwoDays => 2 * 24 * 60 * 60,
-                ExecutionDelay::SevenDays => 7 * 24 * 60 * 60,
-            };
+            // Quorum below the contract default is raised to it
+            if Self::quorum_percentage(&governance_params.quorum_threshold) < Self::quorum_percentage(&self.default_quorum_threshold) {
+                governance_params.quorum_threshold = self.default_quorum_threshold.clone();
+            }
             
-            let voting_end = current_time.checked_add(voting_duration)
-                .ok_or(Error::InvalidProposal)?;
+            // Proposals without their own grace period take the default at creation, so every
+            // passed proposal can eventually be expired and leave the open index
+            if governance_params.grace_period.is_none() {
+                governance_params.grace_period = Some(self.default_grace_period);
+            }
             
-            let execution_time = voting_end.checked_add(execution_delay)
-                .ok_or(Error::InvalidProposal)?;
+            let proposal_id = self.next_proposal_id;
             
             let mut vote_counts = Vec::new();
             for _ in 0..voting_options.options.len() {
                 vote_counts.push(0);
             }
             
+            let status = match governance_params.scheduled_activation {
+                Some(activation) if activation > current_time => ProposalStatus::Scheduled,
+                _ => ProposalStatus::Active,
+            };
+            
             let proposal = Proposal {
                 id: proposal_id,
                 title: title.clone(),
@@ -130,81 +582,203 @@ pub mod treasury_governance {
                 created_at: current_time,
                 voting_end,
                 execution_time,
-                status: ProposalStatus::Active,
+                status,
                 vote_counts,
                 total_voters: 0,
+                endorsement_count: 0,
+                suspended_at: None,
+                co_authors: co_authors.clone(),
+                executed_by: None,
+                executed_at: None,
+                revision_of: None,
+                voters_snapshot: self.total_voters,
+                winning_option: None,
             };
             
             // Store proposal
-            self.proposals.insert(proposal_id, &proposal);
+            self.store_proposal(&proposal);
             self.next_proposal_id += 1;
-            self.proposal_count += 1;
+            self.on_proposal_created(&proposal);
             
             // Emit event
             self.env().emit_event(ProposalCreated {
                 proposal_id,
                 proposer: caller,
                 title,
+                co_authors,
             });
             
             Ok(proposal_id)
         }
 
-        /// Vote on a proposal
+        /// Check whether an account could create a proposal right now, returning the
+        /// specific gating error (paused, too many active proposals, ...) otherwise
         #[ink(message)]
-        pub fn vote(&mut self, proposal_id: u32, choice: VoteChoice) -> Result<()> {
+        pub fn can_create_proposal(&self, proposer: H160) -> Result<bool> {
+            let current_time = self.current_time();
+            self.check_can_create(proposer, current_time)?;
+            Ok(true)
+        }
+
+        /// Supersede an active proposal that has no votes yet with a revised version,
+        /// keeping its type, governance parameters and co-authors (proposer only).
+        /// The original is marked `Cancelled` and linked from the revision.
+        #[ink(message)]
+        pub fn revise_proposal(&mut self, proposal_id: u32, title: String, description: String, voting_options: VotingOptions) -> Result<u32> {
             let caller = self.env().caller();
-            let current_time = self.env().block_timestamp() as u32;
-            
-            // Get the proposal
-            let mut proposal = self.proposals.get(proposal_id).ok_or(Error::ProposalNotFound)?;
+            let mut original = self.proposals.get(proposal_id).ok_or(Error::ProposalNotFound)?;
             
-            // Validate proposal is active
-            if proposal.status != ProposalStatus::Active {
+            if original.proposer != caller {
+                return Err(Error::NotAuthorized);
+            }
+            if original.status != ProposalStatus::Active {
                 return Err(Error::ProposalNotActive);
             }
+            if original.total_voters > 0 {
+                return Err(Error::AlreadyVoted);
+            }
             
-            // Validate voting period has not ended
-            if current_time > proposal.voting_end {
-                return Err(Error::VotingPeriodEnded);
+            let revision_id = self.create_proposal(
+                title,
+                description,
+                original.proposal_type.clone(),
+                original.governance_params.clone(),
+                voting_options,
+                original.co_authors.clone(),
+            )?;
+            
+            // Link the revision back to the original and retire the original
+            if let Some(mut revision) = self.proposals.get(revision_id) {
+                revision.revision_of = Some(proposal_id);
+                self.store_proposal(&revision);
             }
+            original.status = ProposalStatus::Cancelled;
+            self.store_proposal(&original);
+            self.revised_as.insert(proposal_id, &revision_id);
             
-            // Check if user is registered as a voter
-            if !self.is_voter_registered(caller) {
-                return Err(Error::NotAuthorized);
+            self.env().emit_event(ProposalCancelled {
+                proposal_id,
+                cancelled_by: caller,
+            });
+            self.env().emit_event(ProposalRevised {
+                proposal_id,
+                revision_id,
+            });
+            
+            Ok(revision_id)
+        }
+
+        /// Get every version of a proposal, from the first to the latest revision
+        #[ink(message)]
+        pub fn get_revision_chain(&self, proposal_id: u32) -> Vec<u32> {
+            let Some(mut proposal) = self.proposals.get(proposal_id) else {
+                return Vec::new();
+            };
+            
+            // Walk back to the first version
+            while let Some(previous) = proposal.revision_of.and_then(|id| self.proposals.get(id)) {
+                proposal = previous;
             }
             
-            // Prevent double voting
-            if self.votes.contains((proposal_id, caller)) {
-                return Err(Error::AlreadyVoted);
+            // Then forward through each revision
+            let mut chain = ink::prelude::vec![proposal.id];
+            let mut current = proposal.id;
+            while let Some(next) = self.revised_as.get(current) {
+                chain.push(next);
+                current = next;
             }
             
-            // Validate option index
-            if choice.option_index as usize >= proposal.voting_options.options.len() {
-                return Err(Error::InvalidProposal);
+            chain
+        }
+
+        /// Check proposal parameters without creating anything, returning the
+        /// same error `create_proposal` would
+        #[ink(message)]
+        pub fn validate_proposal_params(&self, title: String, description: String, proposal_type: ProposalType, voting_options: VotingOptions, governance_params: GovernanceParameters) -> Result<()> {
+            let current_time = self.current_time();
+            self.validate_proposal(&title, &description, &proposal_type, &voting_options, &governance_params, current_time)?;
+            Ok(())
+        }
+
+        /// Vote on a proposal
+        #[ink(message)]
+        pub fn vote(&mut self, proposal_id: u32, choice: VoteChoice) -> Result<()> {
+            self.cast_vote(proposal_id, choice, None)
+        }
+
+        /// Register the caller if needed and vote in the same call. Registration rules and
+        /// probation still apply, and a failed vote undoes the registration.
+        #[ink(message)]
+        pub fn register_and_vote(&mut self, proposal_id: u32, choice: VoteChoice) -> Result<()> {
+            if !self.is_voter_registered(self.env().caller()) {
+                self.register_voter()?;
+            }
+            self.cast_vote(proposal_id, choice, None)
+        }
+
+        /// Vote on a proposal pinned to an eligibility root, proving the caller's
+        /// inclusion in the Merkle tree of eligible addresses instead of registering
+        #[ink(message)]
+        pub fn vote_with_proof(&mut self, proposal_id: u32, choice: VoteChoice, proof: Vec<[u8; 32]>) -> Result<()> {
+            self.cast_vote(proposal_id, choice, Some(proof))
+        }
+
+        /// Record a ballot, checking registration or, when given, an eligibility proof
+        fn cast_vote(&mut self, proposal_id: u32, choice: VoteChoice, proof: Option<Vec<[u8; 32]>>) -> Result<()> {
+            self.ensure_not_paused()?;
+            let caller = self.env().caller();
+            let current_time = self.current_time();
+            
+            // Get the proposal
+            let mut proposal = self.proposals.get(proposal_id).ok_or(Error::ProposalNotFound)?;
+            Self::activate_if_due(&mut proposal, current_time);
+            
+            // Validate the proposal is open and the caller may vote on it
+            match proof {
+                None => self.check_can_vote(&proposal, caller, current_time)?,
+                Some(proof) => {
+                    let eligibility = self.proposal_eligibility.get(proposal_id).ok_or(Error::NotAuthorized)?;
+                    self.check_ballot_open(&proposal, caller, current_time)?;
+                    if !Self::verify_eligibility(&eligibility.root, caller, &proof) {
+                        return Err(Error::InvalidProof);
+                    }
+                }
+            }
+            
+            // Validate the chosen option
+            Self::check_choice(&proposal, &choice)?;
+            
+            // Delegators who have not voted on this proposal yet add their weight to the ballot
+            let delegated = self.claim_delegated_weight(&proposal, caller, current_time)?;
+            let weight = self.capped_power(self.own_voting_weight(caller, current_time)?.saturating_add(delegated));
+            
+            // Staking voters without stake carry no weight and must not count toward turnout
+            if weight == 0 && self.weight_source == WeightSource::Stake {
+                return Err(Error::InsufficientVotingPower);
             }
             
             // Create vote record
-            let vote = Vote {
-                voter: caller,
-                choice: choice.clone(),
+            let vote = StoredVote {
+                option_index: u16::try_from(choice.option_index).map_err(|_| Error::InvalidProposal)?,
                 timestamp: current_time,
-                weight: 1, // Default weight of 1, can be extended for weighted voting
+                weight,
             };
             
-            // Store vote record
-            self.votes.insert((proposal_id, caller), &vote);
-            
             // Update vote counts
             if let Some(vote_count) = proposal.vote_counts.get_mut(choice.option_index as usize) {
-                *vote_count += 1;
+                *vote_count = vote_count.checked_add(weight).ok_or(Error::InvalidProposal)?;
             }
             
+            // Store vote record and index the voter
+            self.votes.insert((proposal_id, caller), &vote);
+            self.proposal_voters.insert((proposal_id, proposal.total_voters), &caller);
+            self.on_vote_cast(proposal_id, caller, choice.option_index, weight, current_time)?;
+            
             // Update total voters
             proposal.total_voters += 1;
             
             // Update proposal in storage
-            self.proposals.insert(proposal_id, &proposal);
+            self.store_proposal(&proposal);
             
             // Emit vote event
             self.env().emit_event(VoteCast {
@@ -212,306 +786,3121 @@ pub mod treasury_governance {
                 voter: caller,
                 option_index: choice.option_index,
                 option_text: choice.option_text,
-                weight: 1,
+                weight,
             });
             
+            self.refresh_decision_state(&proposal, current_time);
+            
             Ok(())
         }
 
        
 
-        /// Update proposal status based on voting results and quorum
+        /// Check whether an account could vote on a proposal right now, returning
+        /// the error `vote` would fail with (e.g. `VoterInProbation`) otherwise
         #[ink(message)]
-        pub fn update_proposal_status(&mut self, proposal_id: u32) -> Result<ProposalStatus> {
-            let current_time = self.env().block_timestamp() as u32;
+        pub fn can_vote(&self, proposal_id: u32, voter: H160) -> Result<()> {
+            let current_time = self.current_time();
             
-            // Get the proposal
             let mut proposal = self.proposals.get(proposal_id).ok_or(Error::ProposalNotFound)?;
+            Self::activate_if_due(&mut proposal, current_time);
             
-            // Only update if proposal is still active
-            if proposal.status != ProposalStatus::Active {
-                return Ok(proposal.status);
-            }
-            
-            // Check if voting period has ended
-            if current_time <= proposal.voting_end {
-                return Err(Error::ProposalNotReadyForExecution);
-            }
-            
-            // Calculate quorum requirements
-            let quorum_percentage = match proposal.governance_params.quorum_threshold {
-                QuorumThreshold::Five => 5,
-                QuorumThreshold::Ten => 10,
-                QuorumThreshold::Twenty => 20,
-                QuorumThreshold::TwentyFive => 25,
-            };
+            self.check_can_vote(&proposal, voter, current_time)
+        }
+
+        /// Move the caller's ballot on an active proposal to another option before voting ends.
+        /// The ballot keeps the weight it was cast with.
+        #[ink(message)]
+        pub fn change_vote(&mut self, proposal_id: u32, new_choice: VoteChoice) -> Result<()> {
+            self.ensure_not_paused()?;
+            let caller = self.env().caller();
+            let current_time = self.current_time();
             
-            // Calculate required votes for quorum
-            let required_votes = (self.total_voters * quorum_percentage) / 100;
+            let mut proposal = self.proposals.get(proposal_id).ok_or(Error::ProposalNotFound)?;
+            Self::activate_if_due(&mut proposal, current_time);
             
-            // Check if quorum is met
-            if proposal.total_voters < required_votes {
-                proposal.status = ProposalStatus::Rejected;
-                self.proposals.insert(proposal_id, &proposal);
-                
-                self.env().emit_event(ProposalExecuted {
-                    proposal_id,
-                    status: ProposalStatus::Rejected,
-                });
-                
-                return Ok(ProposalStatus::Rejected);
+            if proposal.status != ProposalStatus::Active {
+                return Err(Error::ProposalNotActive);
+            }
+            if current_time > proposal.voting_end {
+                return Err(Error::VotingPeriodEnded);
             }
             
-            // Find the winning option (highest vote count)
-            let mut max_votes = 0;
-            let mut tie_count = 0;
+            let mut vote = self.votes.get((proposal_id, caller)).ok_or(Error::NotVoted)?;
             
-            for &vote_count in &proposal.vote_counts {
-                if vote_count > max_votes {
-                    max_votes = vote_count;
-                    tie_count = 1;
-                } else if vote_count == max_votes && vote_count > 0 {
-                    tie_count += 1;
-                }
+            // Validate the chosen option
+            Self::check_choice(&proposal, &new_choice)?;
+            if new_choice.option_index == vote.option_index as u32 {
+                return Err(Error::SameVote);
             }
             
-            // Handle ties - if there's a tie for the highest vote count, mark as rejected
-            if tie_count > 1 {
-                proposal.status = ProposalStatus::Rejected;
-                self.proposals.insert(proposal_id, &proposal);
-                
-                self.env().emit_event(ProposalExecuted {
-                    proposal_id,
-                    status: ProposalStatus::Rejected,
-                });
-                
-                return Ok(ProposalStatus::Rejected);
+            // Move the ballot's weight from the old option to the new one
+            let old_index = vote.option_index as u32;
+            if let Some(vote_count) = proposal.vote_counts.get_mut(old_index as usize) {
+                *vote_count = vote_count.saturating_sub(vote.weight);
             }
-            
-            // If we have a clear winner and quorum is met, mark as passed
-            if max_votes > 0 {
-                proposal.status = ProposalStatus::Passed;
-                self.proposals.insert(proposal_id, &proposal);
-                
-                self.env().emit_event(ProposalExecuted {
-                    proposal_id,
-                    status: ProposalStatus::Passed,
-                });
-                
-                return Ok(ProposalStatus::Passed);
+            if let Some(vote_count) = proposal.vote_counts.get_mut(new_choice.option_index as usize) {
+                *vote_count = vote_count.checked_add(vote.weight).ok_or(Error::InvalidProposal)?;
             }
             
-            // If no votes were cast, mark as rejected
-            proposal.status = ProposalStatus::Rejected;
-            self.proposals.insert(proposal_id, &proposal);
+            vote.option_index = u16::try_from(new_choice.option_index).map_err(|_| Error::InvalidProposal)?;
+            vote.timestamp = current_time;
+            self.votes.insert((proposal_id, caller), &vote);
+            self.on_vote_changed(proposal_id, caller, new_choice.option_index, vote.weight, current_time);
             
-            self.env().emit_event(ProposalExecuted {
+            self.store_proposal(&proposal);
+            
+            self.env().emit_event(VoteChanged {
                 proposal_id,
-                status: ProposalStatus::Rejected,
+                voter: caller,
+                old_index,
+                new_index: new_choice.option_index,
             });
             
-            Ok(ProposalStatus::Rejected)
+            self.refresh_decision_state(&proposal, current_time);
+            
+            Ok(())
         }
 
-        /// Execute a passed proposal 
+        /// Update proposal status based on voting results and quorum
         #[ink(message)]
-        pub fn execute_proposal(&mut self, proposal_id: u32) -> Result<()> {
-            let current_time = self.env().block_timestamp() as u32;
+        pub fn update_proposal_status(&mut self, proposal_id: u32) -> Result<ProposalStatus> {
+            let current_time = self.current_time();
             
             // Get the proposal
             let mut proposal = self.proposals.get(proposal_id).ok_or(Error::ProposalNotFound)?;
+            Self::activate_if_due(&mut proposal, current_time);
             
-            // Check if proposal is passed and ready for execution
-            if proposal.status != ProposalStatus::Passed {
+            // Scheduled proposals have not started voting yet and suspended ones are on hold
+            if proposal.status == ProposalStatus::Scheduled || proposal.status == ProposalStatus::Suspended {
                 return Err(Error::ProposalNotReadyForExecution);
             }
             
-            // Check if execution time has been reached
-            if current_time < proposal.execution_time {
-                return Err(Error::ProposalNotReadyForExecution);
+            if proposal.status == ProposalStatus::Cancelled {
+                return Err(Error::ProposalNotActive);
             }
             
-            // Mark as executed
-            proposal.status = ProposalStatus::Executed;
-            self.proposals.insert(proposal_id, &proposal);
+            // Only update if proposal is still active
+            if proposal.status != ProposalStatus::Active {
+                return Ok(proposal.status);
+            }
+            Self::check_tally_aligned(&proposal)?;
             
-            self.env().emit_event(ProposalExecuted {
-                proposal_id,
-                status: ProposalStatus::Executed,
-            });
+            // With a confirmation period, the decision state drives the outcome
+            if self.confirmation_period > 0 {
+                return self.finalize_by_confirmation(proposal, current_time);
+            }
             
-            Ok(())
+            // Check if voting period has ended
+            if current_time <= proposal.voting_end {
+                return Err(Error::ProposalNotReadyForExecution);
+            }
+            
+            // Apply the same decision the simulation reports
+            let outcome = self.decide_outcome(&proposal, current_time);
+            Ok(self.conclude_voting(proposal, outcome.status, current_time))
         }
 
-        /// Register a user as a global voter
+        /// Finalize up to `max` active proposals with an ID below `id` whose voting has ended,
+        /// oldest first, returning how many were finalized (owner only)
         #[ink(message)]
-        pub fn register_voter(&mut self) -> Result<()> {
-            let caller = self.env().caller();
-            
-            // Check if user is already registered
-            if self.is_voter_registered(caller) {
-                return Err(Error::AlreadyRegistered);
-            }
+        pub fn finalize_before(&mut self, id: u32, max: u32) -> Result<u32> {
+            self.ensure_owner()?;
+            let current_time = self.current_time();
             
-            // Register the voter globally
-            self.registered_voters.insert(caller, &true);
+            let mut candidates: Vec<u32> = self.open_proposals.iter()
+                .copied()
+                .filter(|&proposal_id| proposal_id < id)
+                .collect();
+            candidates.sort_unstable();
             
-            // Increment total voter count
-            self.total_voters += 1;
+            let mut outcomes: Vec<ProposalStatus> = Vec::new();
+            for proposal_id in candidates {
+                if outcomes.len() as u32 >= max {
+                    break;
+                }
+                let Some(mut proposal) = self.proposals.get(proposal_id) else {
+                    continue;
+                };
+                Self::activate_if_due(&mut proposal, current_time);
+                if proposal.status != ProposalStatus::Active || current_time <= proposal.voting_end {
+                    continue;
+                }
+                // Proposals still confirming are left for a later run
+                if let Ok(status) = self.update_proposal_status(proposal_id) {
+                    outcomes.push(status);
+                }
+            }
             
-            Ok(())
-        }
-
-        /// Check if a user is registered as a voter
-        #[ink(message)]
-        pub fn is_voter_registered(&self, user: H160) -> bool {
-            self.registered_voters.get(user).unwrap_or(false)
-        }
-
-
-        /// Get a proposal by ID
-        #[ink(message)]
-        pub fn get_proposal(&self, proposal_id: u32) -> Option<Proposal> {
-            self.proposals.get(proposal_id)
-        }
-
-        /// Get the total number of proposals
-        #[ink(message)]
-        pub fn get_proposal_count(&self) -> u32 {
-            self.proposal_count
-        }
-
-        /// Get user's vote on a proposal
-        #[ink(message)]
-        pub fn get_user_vote(&self, proposal_id: u32, user: H160) -> Option<Vote> {
-            self.votes.get((proposal_id, user))
+            self.emit_batch_finalized(&outcomes);
+            Ok(outcomes.len() as u32)
         }
 
-        /// Get contract statistics (total, active, executed proposals)
+        /// Finalize and then execute each listed proposal as far as it is due, returning the
+        /// resulting status per proposal. Repeated and unknown IDs are skipped.
         #[ink(message)]
-        pub fn get_stats(&self) -> (u32, u32, u32) {
-            let mut active_count = 0;
-            let mut executed_count = 0;
-            
-            // Count active and executed proposals
-            for i in 1..self.next_proposal_id {
-                if let Some(proposal) = self.proposals.get(i) {
-                    match proposal.status {
-                        ProposalStatus::Active => active_count += 1,
-                        ProposalStatus::Executed => executed_count += 1,
-                        _ => {}
+        pub fn process_proposals(&mut self, ids: Vec<u32>) -> Vec<(u32, ProposalStatus)> {
+            let mut processed: Vec<(u32, ProposalStatus)> = Vec::new();
+            let mut outcomes: Vec<ProposalStatus> = Vec::new();
+            for proposal_id in ids.into_iter().take(MAX_PAGE_SIZE as usize) {
+                if processed.iter().any(|(id, _)| *id == proposal_id) {
+                    continue;
+                }
+                let Some(previous) = self.proposals.get(proposal_id).map(|proposal| proposal.status) else {
+                    continue;
+                };
+                
+                // Proposals that are not due yet keep their status; the failures are expected
+                if let Ok(status) = self.update_proposal_status(proposal_id) {
+                    if status != previous {
+                        outcomes.push(status);
                     }
                 }
+                let _ = self.execute_proposal(proposal_id);
+                
+                if let Some(proposal) = self.proposals.get(proposal_id) {
+                    processed.push((proposal_id, proposal.status));
+                }
             }
             
-            (self.proposal_count, active_count, executed_count)
+            self.emit_batch_finalized(&outcomes);
+            processed
         }
 
-        /// Get the total number of registered voters
+        /// Report what finalizing an active proposal would produce if voting closed right now,
+        /// including the check that currently fails, without changing any state
         #[ink(message)]
-        pub fn get_total_voters(&self) -> u32 {
-            self.total_voters
+        pub fn simulate_finalization(&self, proposal_id: u32) -> Result<SimulatedOutcome> {
+            let current_time = self.current_time();
+            
+            let mut proposal = self.proposals.get(proposal_id).ok_or(Error::ProposalNotFound)?;
+            Self::activate_if_due(&mut proposal, current_time);
+            
+            if proposal.status != ProposalStatus::Active {
+                return Err(Error::ProposalNotActive);
+            }
+            Self::check_tally_aligned(&proposal)?;
+            
+            let mut outcome = self.decide_outcome(&proposal, current_time);
+            
+            // With a confirmation period, passing also needs a completed confirmation clock
+            if self.confirmation_period > 0 && outcome.status == ProposalStatus::Passed {
+                let confirmed = match self.decision_states.get(proposal_id) {
+                    Some(DecisionState::Confirming { since }) => current_time >= since.saturating_add(self.confirmation_period),
+                    _ => false,
+                };
+                if !confirmed {
+                    outcome.status = ProposalStatus::Active;
+                    outcome.failing_check = Some(FinalizationCheck::Confirmation);
+                }
+            }
+            
+            Ok(outcome)
         }
 
-        /// Check if proposal has reached quorum
+        /// Recompute a proposal's tally from its raw vote records, `limit` voters at a time.
+        /// Chunks must be audited in order starting from offset 0, which restarts the audit;
+        /// once every voter is covered the result is compared with the stored counts (guardian only).
         #[ink(message)]
-        pub fn has_reached_quorum(&self, proposal_id: u32) -> Result<bool> {
+        pub fn audit_tally(&mut self, proposal_id: u32, offset: u32, limit: u32) -> Result<AuditReport> {
+            self.ensure_guardian()?;
             let proposal = self.proposals.get(proposal_id).ok_or(Error::ProposalNotFound)?;
             
-            let quorum_percentage = match proposal.governance_params.quorum_threshold {
-                QuorumThreshold::Five => 5,
-                QuorumThreshold::Ten => 10,
-                QuorumThreshold::Twenty => 20,
-                QuorumThreshold::TwentyFive => 25,
+            let mut audit = match self.tally_audits.get(proposal_id) {
+                Some(audit) if offset != 0 => audit,
+                _ => TallyAudit { recomputed: ink::prelude::vec![0; proposal.vote_counts.len()], next_index: 0 },
             };
+            if offset != audit.next_index {
+                return Err(Error::InvalidProposal);
+            }
             
-            let required_votes = (self.total_voters * quorum_percentage) / 100;
-            Ok(proposal.total_voters >= required_votes)
+            // Fold this chunk of raw vote records into the running recount
+            let end = offset.saturating_add(limit.min(MAX_PAGE_SIZE)).min(proposal.total_voters);
+            for index in offset..end {
+                let Some(vote) = self.proposal_voters.get((proposal_id, index))
+                    .and_then(|voter| self.votes.get((proposal_id, voter))) else {
+                    continue;
+                };
+                if let Some(count) = audit.recomputed.get_mut(vote.option_index as usize) {
+                    *count = count.saturating_add(vote.weight);
+                }
+            }
+            audit.next_index = end;
+            self.tally_audits.insert(proposal_id, &audit);
+            
+            let complete = end == proposal.total_voters;
+            let consistent = complete.then(|| audit.recomputed == proposal.vote_counts);
+            if consistent == Some(false) {
+                self.env().emit_event(TallyDiscrepancy {
+                    proposal_id,
+                    stored: proposal.vote_counts.clone(),
+                    recomputed: audit.recomputed.clone(),
+                });
+            }
+            
+            Ok(AuditReport {
+                next_offset: end,
+                complete,
+                consistent,
+                stored: proposal.vote_counts,
+                recomputed: audit.recomputed,
+            })
         }
 
-        /// Get proposal results (vote counts and quorum status)
+        /// Replace an active proposal's stored tally with the recount from a complete,
+        /// up-to-date audit (guardian only)
         #[ink(message)]
-        pub fn get_proposal_results(&self, proposal_id: u32) -> Result<(Vec<u128>, bool, u32, u32)> {
-            let proposal = self.proposals.get(proposal_id).ok_or(Error::ProposalNotFound)?;
+        pub fn repair_tally(&mut self, proposal_id: u32) -> Result<()> {
+            self.ensure_guardian()?;
             
-            let quorum_percentage = match proposal.governance_params.quorum_threshold {
-                QuorumThreshold::Five => 5,
-                QuorumThreshold::Ten => 10,
-                QuorumThreshold::Twenty => 20,
-                QuorumThreshold::TwentyFive => 25,
-            };
+            let mut proposal = self.proposals.get(proposal_id).ok_or(Error::ProposalNotFound)?;
+            if proposal.status != ProposalStatus::Active {
+                return Err(Error::ProposalNotActive);
+            }
             
-            let required_votes = (self.total_voters * quorum_percentage) / 100;
-            let has_quorum = proposal.total_voters >= required_votes;
+            // The audit must have covered every voter, including any who voted since it started
+            let audit = self.tally_audits.get(proposal_id).ok_or(Error::InvalidProposal)?;
+            if audit.next_index != proposal.total_voters {
+                return Err(Error::InvalidProposal);
+            }
             
-            Ok((proposal.vote_counts, has_quorum, proposal.total_voters, required_votes))
+            let previous = core::mem::replace(&mut proposal.vote_counts, audit.recomputed.clone());
+            self.tally_audits.remove(proposal_id);
+            self.store_proposal(&proposal);
+            
+            self.env().emit_event(TallyRepaired {
+                proposal_id,
+                previous,
+                repaired: audit.recomputed,
+            });
+            
+            Ok(())
         }
 
-        /// Get voting options for a proposal
+        /// Put an active proposal on hold, stopping its clock (guardian only)
         #[ink(message)]
-        pub fn get_voting_options(&self, proposal_id: u32) -> Result<Vec<String>> {
-            let proposal = self.proposals.get(proposal_id).ok_or(Error::ProposalNotFound)?;
-            Ok(proposal.voting_options.options)
+        pub fn suspend_proposal(&mut self, proposal_id: u32) -> Result<()> {
+            self.ensure_guardian()?;
+            let current_time = self.current_time();
+            
+            let mut proposal = self.proposals.get(proposal_id).ok_or(Error::ProposalNotFound)?;
+            Self::activate_if_due(&mut proposal, current_time);
+            
+            if proposal.status != ProposalStatus::Active {
+                return Err(Error::ProposalNotActive);
+            }
+            
+            proposal.status = ProposalStatus::Suspended;
+            proposal.suspended_at = Some(current_time);
+            self.store_proposal(&proposal);
+            
+            self.env().emit_event(ProposalSuspended {
+                proposal_id,
+                suspended_by: self.env().caller(),
+            });
+            
+            Ok(())
         }
 
-        /// Get detailed results with option names
+        /// Resume a suspended proposal, extending its deadlines by the time spent suspended (guardian only)
         #[ink(message)]
-        pub fn get_detailed_results(&self, proposal_id: u32) -> Result<Vec<(String, u128)>> {
-            let proposal = self.proposals.get(proposal_id).ok_or(Error::ProposalNotFound)?;
+        pub fn resume_proposal(&mut self, proposal_id: u32) -> Result<()> {
+            self.ensure_guardian()?;
+            let current_time = self.current_time();
             
-            let mut results = Vec::new();
-            for (index, &vote_count) in proposal.vote_counts.iter().enumerate() {
-                if let Some(option_text) = proposal.voting_options.options.get(index) {
-                    results.push((option_text.clone(), vote_count));
-                }
+            let mut proposal = self.proposals.get(proposal_id).ok_or(Error::ProposalNotFound)?;
+            
+            if proposal.status != ProposalStatus::Suspended {
+                return Err(Error::ProposalNotActive);
             }
             
-            Ok(results)
+            let suspended_at = proposal.suspended_at.unwrap_or(current_time);
+            let extended_by = current_time.saturating_sub(suspended_at);
+            
+            proposal.voting_end = proposal.voting_end.checked_add(extended_by)
+                .ok_or(Error::InvalidProposal)?;
+            proposal.execution_time = proposal.execution_time.checked_add(extended_by)
+                .ok_or(Error::InvalidProposal)?;
+            proposal.status = ProposalStatus::Active;
+            proposal.suspended_at = None;
+            self.store_proposal(&proposal);
+            
+            self.env().emit_event(ProposalResumed {
+                proposal_id,
+                extended_by,
+            });
+            
+            Ok(())
         }
 
-        /// Get the winning option and vote count
+        /// Withdraw an active proposal before its voting period ends (authors or owner), and
+        /// before the first vote unless its parameters allow otherwise. Ballots cast stay on record.
         #[ink(message)]
-        pub fn get_winning_option(&self, proposal_id: u32) -> Result<Option<(String, u128)>> {
-            let proposal = self.proposals.get(proposal_id).ok_or(Error::ProposalNotFound)?;
+        pub fn cancel_proposal(&mut self, proposal_id: u32) -> Result<()> {
+            let caller = self.env().caller();
+            let current_time = self.current_time();
             
-            let mut max_votes = 0;
-            let mut winning_index = None;
-            let mut tie_count = 0;
+            let mut proposal = self.proposals.get(proposal_id).ok_or(Error::ProposalNotFound)?;
+            Self::activate_if_due(&mut proposal, current_time);
             
-            for (index, &vote_count) in proposal.vote_counts.iter().enumerate() {
-                if vote_count > max_votes {
-                    max_votes = vote_count;
-                    winning_index = Some(index);
-                    tie_count = 1;
-                } else if vote_count == max_votes && vote_count > 0 {
-                    tie_count += 1;
-                }
+            if !proposal.is_author(caller) && caller != self.owner {
+                return Err(Error::NotAuthorized);
+            }
+            if proposal.status != ProposalStatus::Active {
+                return Err(Error::ProposalNotActive);
+            }
+            if current_time > proposal.voting_end {
+                return Err(Error::VotingPeriodEnded);
+            }
+            if proposal.total_voters > 0 && !proposal.governance_params.allow_cancel_after_votes {
+                return Err(Error::VotesAlreadyCast);
             }
             
-            // If there's a tie or no votes, return None
-            if tie_count > 1 || max_votes == 0 {
-                return Ok(None);
+            proposal.status = ProposalStatus::Cancelled;
+            self.store_proposal(&proposal);
+            
+            self.env().emit_event(ProposalCancelled {
+                proposal_id,
+                cancelled_by: caller,
+            });
+            
+            Ok(())
+        }
+
+        /// Clear every vote cast on an active proposal so voting can restart (owner only).
+        /// Rewards already paid stay counted against the proposal's budget, since the funds have left.
+        #[ink(message)]
+        pub fn reset_votes(&mut self, proposal_id: u32) -> Result<()> {
+            self.ensure_owner()?;
+            
+            let mut proposal = self.proposals.get(proposal_id).ok_or(Error::ProposalNotFound)?;
+            
+            if proposal.status != ProposalStatus::Active {
+                return Err(Error::ProposalNotActive);
             }
             
-            // Return the winning option
-            if let Some(index) = winning_index {
-                if let Some(option_text) = proposal.voting_options.options.get(index) {
-                    return Ok(Some((option_text.clone(), max_votes)));
+            // Delete every vote record through the voter index and take back the voters' counts
+            let cleared_votes = proposal.total_voters;
+            for index in 0..cleared_votes {
+                if let Some(voter) = self.proposal_voters.take((proposal_id, index)) {
+                    self.votes.remove((proposal_id, voter));
+                    if let Some(mut info) = self.voter_info_of(voter) {
+                        info.votes_cast = info.votes_cast.saturating_sub(1);
+                        self.store_voter_info(voter, &info);
+                    }
                 }
             }
             
-            Ok(None)
+            for (index, vote_count) in proposal.vote_counts.iter_mut().enumerate() {
+                *vote_count = 0;
+                self.option_last_vote.remove((proposal_id, index as u32));
+            }
+            proposal.total_voters = 0;
+            self.ballot_commitments.remove(proposal_id);
+            self.tally_audits.remove(proposal_id);
+            if let Some(DecisionState::Confirming { .. }) = self.decision_states.take(proposal_id) {
+                self.env().emit_event(LeftConfirming { proposal_id });
+            }
+            self.store_proposal(&proposal);
+            
+            self.env().emit_event(VotesReset {
+                proposal_id,
+                cleared_votes,
+            });
+            
+            Ok(())
         }
 
-        /// Get the next proposal ID
+        /// Execute a passed proposal 
         #[ink(message)]
-        pub fn get_next_proposal_id(&self) -> u32 {
-            self.next_proposal_id
+        pub fn execute_proposal(&mut self, proposal_id: u32) -> Result<()> {
+            self.ensure_not_paused()?;
+            let current_time = self.current_time();
+            
+            // Get the proposal
+            let mut proposal = self.proposals.get(proposal_id).ok_or(Error::ProposalNotFound)?;
+            Self::check_can_execute(&proposal, current_time)?;
+            self.check_oracle_condition(&proposal)?;
+            self.balance_after_execution(&proposal)?;
+            
+            // Mark as executed and record who executed it before any transfer or call,
+            // so a reentrant call finds the proposal already executed
+            let executor = self.env().caller();
+            proposal.status = ProposalStatus::Executed;
+            proposal.executed_by = Some(executor);
+            proposal.executed_at = Some(current_time);
+            self.store_proposal(&proposal);
+            
+            self.on_executed(&proposal, current_time)?;
+            
+            self.env().emit_event(ProposalExecuted {
+                proposal_id,
+                status: ProposalStatus::Executed,
+                executor: Some(executor),
+                ballot_commitment: self.ballot_commitment_of(proposal_id).commitment,
+            });
+            
+            Ok(())
         }
 
-        
-
-        
+        /// Mark a passed proposal Expired once its grace period after `execution_time` has run out
+        #[ink(message)]
+        pub fn expire_proposal(&mut self, proposal_id: u32) -> Result<()> {
+            self.ensure_not_paused()?;
+            let current_time = self.current_time();
+            
+            let mut proposal = self.proposals.get(proposal_id).ok_or(Error::ProposalNotFound)?;
+            if proposal.status != ProposalStatus::Passed || !Self::execution_window_closed(&proposal, current_time) {
+                return Err(Error::ProposalNotReadyForExecution);
+            }
+            
+            proposal.status = ProposalStatus::Expired;
+            self.store_proposal(&proposal);
+            
+            self.env().emit_event(ProposalExpired { proposal_id });
+            
+            Ok(())
+        }
+
+        /// Preview executing a proposal: the treasury balance afterwards and the resulting
+        /// status, running the same checks as `execute_proposal` without moving funds
+        #[ink(message)]
+        pub fn simulate_execution(&self, proposal_id: u32) -> Result<(u128, ProposalStatus)> {
+            self.ensure_not_paused()?;
+            let current_time = self.current_time();
+            
+            let proposal = self.proposals.get(proposal_id).ok_or(Error::ProposalNotFound)?;
+            Self::check_can_execute(&proposal, current_time)?;
+            self.check_oracle_condition(&proposal)?;
+            
+            Ok((self.balance_after_execution(&proposal)?, ProposalStatus::Executed))
+        }
+
+        /// Endorse a proposal to signal support for discussing it.
+        /// Endorsements do not count as votes and have no effect on quorum or outcome.
+        #[ink(message)]
+        pub fn endorse_proposal(&mut self, proposal_id: u32) -> Result<()> {
+            let caller = self.env().caller();
+            
+            if !self.is_voter_registered(caller) {
+                return Err(Error::NotRegistered);
+            }
+            
+            let mut proposal = self.proposals.get(proposal_id).ok_or(Error::ProposalNotFound)?;
+            
+            if proposal.status.is_terminal() {
+                return Err(Error::ProposalNotActive);
+            }
+            
+            if self.endorser_positions.contains((proposal_id, caller)) {
+                return Err(Error::AlreadyEndorsed);
+            }
+            
+            // Append the endorser to the enumerable index
+            let position = proposal.endorsement_count;
+            self.endorsers.insert((proposal_id, position), &caller);
+            self.endorser_positions.insert((proposal_id, caller), &position);
+            
+            proposal.endorsement_count += 1;
+            self.store_proposal(&proposal);
+            
+            self.env().emit_event(ProposalEndorsed {
+                proposal_id,
+                endorser: caller,
+            });
+            
+            Ok(())
+        }
+
+        /// Flag an active proposal as spam or a scam, suspending it for guardian review
+        /// once the flags reach the configured threshold
+        #[ink(message)]
+        pub fn flag_proposal(&mut self, proposal_id: u32) -> Result<()> {
+            let caller = self.env().caller();
+            let current_time = self.current_time();
+            
+            if !self.is_voter_registered(caller) {
+                return Err(Error::NotRegistered);
+            }
+            if self.barred_flaggers.get(caller).unwrap_or(false) {
+                return Err(Error::FlaggingBarred);
+            }
+            
+            let mut proposal = self.proposals.get(proposal_id).ok_or(Error::ProposalNotFound)?;
+            Self::activate_if_due(&mut proposal, current_time);
+            
+            if proposal.status != ProposalStatus::Active {
+                return Err(Error::ProposalNotActive);
+            }
+            if self.has_flagged.get((proposal_id, caller)).unwrap_or(false) {
+                return Err(Error::AlreadyFlagged);
+            }
+            
+            // Append the flagger to the public, enumerable index
+            let previous_flags = self.flag_counts.get(proposal_id).unwrap_or(0);
+            let flags = previous_flags + 1;
+            self.flaggers.insert((proposal_id, previous_flags), &caller);
+            self.has_flagged.insert((proposal_id, caller), &true);
+            self.flag_counts.insert(proposal_id, &flags);
+            
+            self.env().emit_event(ProposalFlagged {
+                proposal_id,
+                flagger: caller,
+            });
+            
+            // Only the flag that crosses the threshold suspends, so a guardian's resume sticks
+            if let Some(required) = self.required_flags() {
+                if previous_flags < required && flags >= required {
+                    proposal.status = ProposalStatus::Suspended;
+                    proposal.suspended_at = Some(current_time);
+                    self.env().emit_event(ProposalAutoSuspended {
+                        proposal_id,
+                        flags,
+                    });
+                }
+            }
+            self.store_proposal(&proposal);
+            
+            Ok(())
+        }
+
+        /// Withdraw a previously given endorsement
+        #[ink(message)]
+        pub fn un_endorse(&mut self, proposal_id: u32) -> Result<()> {
+            let caller = self.env().caller();
+            
+            let mut proposal = self.proposals.get(proposal_id).ok_or(Error::ProposalNotFound)?;
+            
+            if proposal.status.is_terminal() {
+                return Err(Error::ProposalNotActive);
+            }
+            
+            let position = self.endorser_positions.get((proposal_id, caller)).ok_or(Error::NotEndorsed)?;
+            
+            // Swap the last endorser into the freed slot to keep the index dense
+            let last_position = proposal.endorsement_count - 1;
+            if position != last_position {
+                if let Some(last_endorser) = self.endorsers.get((proposal_id, last_position)) {
+                    self.endorsers.insert((proposal_id, position), &last_endorser);
+                    self.endorser_positions.insert((proposal_id, last_endorser), &position);
+                }
+            }
+            self.endorsers.remove((proposal_id, last_position));
+            self.endorser_positions.remove((proposal_id, caller));
+            
+            proposal.endorsement_count -= 1;
+            self.store_proposal(&proposal);
+            
+            self.env().emit_event(EndorsementWithdrawn {
+                proposal_id,
+                endorser: caller,
+            });
+            
+            Ok(())
+        }
+
+        /// Add a proposal to the caller's watchlist
+        #[ink(message)]
+        pub fn watch_proposal(&mut self, proposal_id: u32) -> Result<()> {
+            let caller = self.env().caller();
+            
+            if !self.proposals.contains(proposal_id) {
+                return Err(Error::ProposalNotFound);
+            }
+            
+            let mut watchlist = self.watchlists.get(caller).unwrap_or_default();
+            if watchlist.contains(&proposal_id) {
+                return Err(Error::AlreadyWatching);
+            }
+            if watchlist.len() >= MAX_WATCHLIST_SIZE {
+                return Err(Error::WatchlistFull);
+            }
+            
+            watchlist.push(proposal_id);
+            self.watchlists.insert(caller, &watchlist);
+            
+            Ok(())
+        }
+
+        /// Remove a proposal from the caller's watchlist
+        #[ink(message)]
+        pub fn unwatch_proposal(&mut self, proposal_id: u32) -> Result<()> {
+            let caller = self.env().caller();
+            
+            let mut watchlist = self.watchlists.get(caller).unwrap_or_default();
+            let position = watchlist.iter().position(|&id| id == proposal_id)
+                .ok_or(Error::NotWatching)?;
+            
+            watchlist.remove(position);
+            if watchlist.is_empty() {
+                self.watchlists.remove(caller);
+            } else {
+                self.watchlists.insert(caller, &watchlist);
+            }
+            
+            Ok(())
+        }
+
+        /// Register a user as a global voter, staking any transferred value
+        #[ink(message, payable)]
+        pub fn register_voter(&mut self) -> Result<()> {
+            let caller = self.env().caller();
+            
+            // Contract accounts enable wrapper-based vote buying unless explicitly allowed
+            if !self.allow_contract_voters && self.is_contract_account(caller) {
+                return Err(Error::ContractAccountsNotAllowed);
+            }
+            
+            self.register(caller)?;
+            
+            let stake: u128 = self.env().transferred_value().try_into().map_err(|_| Error::ArithmeticOverflow)?;
+            self.add_stake(caller, stake)
+        }
+
+        /// Add the transferred value to the caller's stake
+        #[ink(message, payable)]
+        pub fn increase_stake(&mut self) -> Result<()> {
+            let caller = self.env().caller();
+            if !self.is_voter_registered(caller) {
+                return Err(Error::NotRegistered);
+            }
+            
+            let amount: u128 = self.env().transferred_value().try_into().map_err(|_| Error::ArithmeticOverflow)?;
+            if amount == 0 {
+                return Err(Error::InvalidAmount);
+            }
+            self.add_stake(caller, amount)
+        }
+
+        /// Get the native tokens an account has staked
+        #[ink(message)]
+        pub fn get_stake(&self, account: H160) -> u128 {
+            self.stakes.get(account).unwrap_or(0)
+        }
+
+        /// Get the sum of all voter stakes
+        #[ink(message)]
+        pub fn get_total_weight(&self) -> u128 {
+            self.total_weight
+        }
+
+        /// Register an account as a voter on its behalf (owner only).
+        /// `allow_contract` admits known-good contracts such as a multisig even when
+        /// contract voters are otherwise disallowed.
+        #[ink(message)]
+        pub fn admin_register_voter(&mut self, account: H160, allow_contract: bool) -> Result<()> {
+            self.ensure_owner()?;
+            
+            if !allow_contract && !self.allow_contract_voters && self.is_contract_account(account) {
+                return Err(Error::ContractAccountsNotAllowed);
+            }
+            
+            self.register(account)
+        }
+
+        /// Leave the voter set, ending delegations to and from the caller and returning
+        /// any stake. Ballots already cast stay on record, so a stake that weighs on an
+        /// unfinalized proposal stays locked until that proposal is finalized.
+        #[ink(message)]
+        pub fn deregister_voter(&mut self) -> Result<()> {
+            let caller = self.env().caller();
+            let info = self.voter_info_of(caller).ok_or(Error::NotRegistered)?;
+            
+            // A refunded stake must not keep counting in live tallies, or it could be re-staked and voted again
+            if self.stakes.get(caller).unwrap_or(0) > 0 && self.has_open_ballot(caller) {
+                return Err(Error::StakeLocked);
+            }
+            
+            // End the caller's own delegation and every delegation to the caller
+            let mut ended = Vec::new();
+            if self.remove_delegation(caller).is_some() {
+                ended.push(caller);
+            }
+            while let Some(delegator) = self.delegators.get((caller, 0)) {
+                self.remove_delegation(delegator);
+                ended.push(delegator);
+            }
+            for delegator in ended {
+                self.env().emit_event(DelegateChanged {
+                    delegator,
+                    delegate: None,
+                });
+            }
+            
+            // Return the stake, which is not part of the treasury
+            let stake = self.stakes.take(caller).unwrap_or(0);
+            if stake > 0 {
+                self.total_weight = self.total_weight.saturating_sub(stake);
+                self.env().transfer(caller, U256::from(stake)).map_err(|_| Error::ExecutionFailed)?;
+            }
+            
+            // Drop the record in either format, and its probation entry
+            self.voter_info.remove(caller);
+            self.registered_voters.remove(caller);
+            self.voter_registered_at.remove(caller);
+            if let Some(position) = self.recent_registrations.iter().position(|&registered_at| registered_at == info.registered_at) {
+                self.recent_registrations.remove(position);
+            }
+            self.total_voters = self.total_voters.saturating_sub(1);
+            
+            self.env().emit_event(VoterDeregistered { voter: caller });
+            
+            Ok(())
+        }
+
+        /// Check if a user is registered as a voter
+        #[ink(message)]
+        pub fn is_voter_registered(&self, user: H160) -> bool {
+            self.voter_info_of(user).is_some()
+        }
+
+        /// Let a registered voter cast the caller's weight on proposals they vote on from now on,
+        /// replacing any previous delegation
+        #[ink(message)]
+        pub fn delegate_to(&mut self, delegate: H160) -> Result<()> {
+            let caller = self.env().caller();
+            
+            if !self.is_voter_registered(caller) {
+                return Err(Error::NotRegistered);
+            }
+            if delegate == caller || !self.is_voter_registered(delegate) {
+                return Err(Error::InvalidDelegation);
+            }
+            // Delegation is one level deep: delegates cannot delegate onward, which could close a
+            // cycle, and accounts holding delegations cannot pass them on, which would orphan them
+            if self.delegations.contains(delegate) || self.delegated_counts.get(caller).unwrap_or(0) > 0 {
+                return Err(Error::InvalidDelegation);
+            }
+            if self.delegations.get(caller) == Some(delegate) {
+                return Ok(());
+            }
+            
+            let position = self.delegated_counts.get(delegate).unwrap_or(0);
+            if position >= MAX_DELEGATORS {
+                return Err(Error::InvalidDelegation);
+            }
+            
+            self.remove_delegation(caller);
+            self.delegations.insert(caller, &delegate);
+            self.delegators.insert((delegate, position), &caller);
+            self.delegator_positions.insert(caller, &position);
+            self.delegated_counts.insert(delegate, &(position + 1));
+            
+            self.env().emit_event(DelegateChanged {
+                delegator: caller,
+                delegate: Some(delegate),
+            });
+            
+            Ok(())
+        }
+
+        /// Stop delegating the caller's weight; ballots already cast with it are kept
+        #[ink(message)]
+        pub fn revoke_delegation(&mut self) -> Result<()> {
+            let caller = self.env().caller();
+            self.remove_delegation(caller).ok_or(Error::InvalidDelegation)?;
+            
+            self.env().emit_event(DelegateChanged {
+                delegator: caller,
+                delegate: None,
+            });
+            
+            Ok(())
+        }
+
+        /// Get the account an account delegates its weight to, if any
+        #[ink(message)]
+        pub fn get_delegate(&self, account: H160) -> Option<H160> {
+            self.delegations.get(account)
+        }
+
+        /// Get the combined weight of the accounts currently delegating to an account, before the power cap
+        #[ink(message)]
+        pub fn get_delegated_weight(&self, account: H160) -> Result<u128> {
+            let current_time = self.current_time();
+            self.delegated_weight(account, current_time)
+        }
+
+        /// Get a voter's registration metadata and activity
+        #[ink(message)]
+        pub fn get_voter_info(&self, account: H160) -> Option<VoterInfo> {
+            self.voter_info_of(account)
+        }
+
+        /// Set a registered voter's membership tier (owner only)
+        #[ink(message)]
+        pub fn set_voter_tier(&mut self, account: H160, tier: VoterTier) -> Result<()> {
+            self.ensure_owner()?;
+            let mut info = self.voter_info_of(account).ok_or(Error::NotRegistered)?;
+            info.tier = tier;
+            self.store_voter_info(account, &info);
+            Ok(())
+        }
+
+        /// Upgrade legacy registration entries of the given accounts to `VoterInfo` records,
+        /// returning how many were upgraded
+        #[ink(message)]
+        pub fn migrate_voter_records(&mut self, accounts: Vec<H160>) -> u32 {
+            let mut migrated = 0;
+            for account in accounts.into_iter().take(MAX_PAGE_SIZE as usize) {
+                if self.voter_info.contains(account) {
+                    continue;
+                }
+                if let Some(info) = self.voter_info_of(account) {
+                    self.store_voter_info(account, &info);
+                    migrated += 1;
+                }
+            }
+            migrated
+        }
+
+
+        /// Get a proposal by ID
+        #[ink(message)]
+        pub fn get_proposal(&self, proposal_id: u32) -> Option<Proposal> {
+            self.proposals.get(proposal_id)
+        }
+
+        /// Get several proposals by ID in the requested order, with None for unknown IDs.
+        /// At most `MAX_PAGE_SIZE` IDs are looked up; the rest are ignored.
+        #[ink(message)]
+        pub fn get_proposals(&self, ids: Vec<u32>) -> Vec<Option<Proposal>> {
+            ids.into_iter()
+                .take(MAX_PAGE_SIZE as usize)
+                .map(|proposal_id| self.proposals.get(proposal_id))
+                .collect()
+        }
+
+        /// Get up to `limit` proposals in ID order, starting at `start_id` and skipping unknown IDs.
+        /// The limit is capped at `MAX_PAGE_SIZE`.
+        #[ink(message)]
+        pub fn list_proposals(&self, start_id: u32, limit: u32) -> Vec<Proposal> {
+            (start_id..self.next_proposal_id)
+                .filter_map(|proposal_id| self.proposals.get(proposal_id))
+                .take(limit.min(MAX_PAGE_SIZE) as usize)
+                .collect()
+        }
+
+        /// Get up to `limit` proposals with the given status in ID order, scanning at most
+        /// `MAX_PAGE_SIZE` IDs from `start_id`, and the ID to continue the scan from.
+        /// Scheduled proposals whose voting has opened count as active.
+        #[ink(message)]
+        pub fn get_proposals_by_status(&self, status: ProposalStatus, start_id: u32, limit: u32) -> (Vec<Proposal>, u32) {
+            let current_time = self.current_time();
+            let end = self.next_proposal_id.min(start_id.saturating_add(MAX_PAGE_SIZE)).max(start_id);
+            
+            let mut matching = Vec::new();
+            for proposal_id in start_id..end {
+                if matching.len() >= limit.min(MAX_PAGE_SIZE) as usize {
+                    return (matching, proposal_id);
+                }
+                let Some(mut proposal) = self.proposals.get(proposal_id) else {
+                    continue;
+                };
+                Self::activate_if_due(&mut proposal, current_time);
+                if proposal.status == status {
+                    matching.push(proposal);
+                }
+            }
+            (matching, end)
+        }
+
+        /// Get the IDs `list_proposals` would return for the same page, without loading the proposals
+        #[ink(message)]
+        pub fn get_proposal_ids(&self, start_id: u32, limit: u32) -> Vec<u32> {
+            (start_id..self.next_proposal_id)
+                .filter(|&proposal_id| self.proposals.contains(proposal_id))
+                .take(limit.min(MAX_PAGE_SIZE) as usize)
+                .collect()
+        }
+
+        /// Get up to `limit` proposal IDs with their voter counts, highest turnout first and
+        /// lower IDs first on equal turnout. The limit is capped at `MAX_PAGE_SIZE`.
+        #[ink(message)]
+        pub fn get_proposals_by_participation(&self, limit: u32) -> Vec<(u32, u32)> {
+            let limit = limit.min(MAX_PAGE_SIZE) as usize;
+            
+            // Keep only the best `limit` entries, sorted, while scanning
+            let mut ranked: Vec<(u32, u32)> = Vec::with_capacity(limit + 1);
+            for proposal_id in 1..self.next_proposal_id {
+                let Some(proposal) = self.proposals.get(proposal_id) else {
+                    continue;
+                };
+                let position = ranked.iter()
+                    .position(|&(_, total_voters)| total_voters < proposal.total_voters)
+                    .unwrap_or(ranked.len());
+                if position < limit {
+                    ranked.insert(position, (proposal_id, proposal.total_voters));
+                    ranked.truncate(limit);
+                }
+            }
+            
+            ranked
+        }
+
+        /// Get the total number of proposals
+        #[ink(message)]
+        pub fn get_proposal_count(&self) -> u32 {
+            self.proposal_count
+        }
+
+        /// Get the number of proposals, leaving out cancelled ones and originals superseded by a revision
+        #[ink(message)]
+        pub fn get_effective_proposal_count(&self) -> u32 {
+            self.proposal_count.saturating_sub(self.status_counts.cancelled)
+        }
+
+        /// Get a page of the accounts endorsing a proposal
+        #[ink(message)]
+        pub fn get_endorsements(&self, proposal_id: u32, offset: u32, limit: u32) -> Result<Vec<H160>> {
+            let proposal = self.proposals.get(proposal_id).ok_or(Error::ProposalNotFound)?;
+            
+            let end = offset.saturating_add(limit.min(MAX_PAGE_SIZE)).min(proposal.endorsement_count);
+            let mut endorsers = Vec::new();
+            for position in offset..end {
+                if let Some(endorser) = self.endorsers.get((proposal_id, position)) {
+                    endorsers.push(endorser);
+                }
+            }
+            
+            Ok(endorsers)
+        }
+
+        /// Get a page of the accounts that flagged a proposal
+        #[ink(message)]
+        pub fn get_flaggers(&self, proposal_id: u32, offset: u32, limit: u32) -> Result<Vec<H160>> {
+            if !self.proposals.contains(proposal_id) {
+                return Err(Error::ProposalNotFound);
+            }
+            
+            let end = offset.saturating_add(limit.min(MAX_PAGE_SIZE)).min(self.get_flag_count(proposal_id));
+            let mut flaggers = Vec::new();
+            for position in offset..end {
+                if let Some(flagger) = self.flaggers.get((proposal_id, position)) {
+                    flaggers.push(flagger);
+                }
+            }
+            
+            Ok(flaggers)
+        }
+
+        /// Get the number of flags a proposal has received
+        #[ink(message)]
+        pub fn get_flag_count(&self, proposal_id: u32) -> u32 {
+            self.flag_counts.get(proposal_id).unwrap_or(0)
+        }
+
+        /// Check if an account has been barred from flagging proposals
+        #[ink(message)]
+        pub fn is_flagging_barred(&self, account: H160) -> bool {
+            self.barred_flaggers.get(account).unwrap_or(false)
+        }
+
+        /// Get summaries of a page of the proposals an account is watching
+        #[ink(message)]
+        pub fn get_watchlist(&self, account: H160, offset: u32, limit: u32) -> Vec<ProposalSummary> {
+            let watchlist = self.watchlists.get(account).unwrap_or_default();
+            
+            watchlist.iter()
+                .skip(offset as usize)
+                .take(limit.min(MAX_PAGE_SIZE) as usize)
+                .filter_map(|&id| self.proposals.get(id))
+                .map(|proposal| ProposalSummary::from(&proposal))
+                .collect()
+        }
+
+        /// Get the proposer followed by the co-authors of a proposal
+        #[ink(message)]
+        pub fn get_authors(&self, proposal_id: u32) -> Result<Vec<H160>> {
+            let proposal = self.proposals.get(proposal_id).ok_or(Error::ProposalNotFound)?;
+            
+            let mut authors = Vec::new();
+            authors.push(proposal.proposer);
+            authors.extend(proposal.co_authors);
+            Ok(authors)
+        }
+
+        /// Get who executed a proposal and when, if it has been executed
+        #[ink(message)]
+        pub fn get_execution_record(&self, proposal_id: u32) -> Result<Option<(H160, Timestamp)>> {
+            let proposal = self.proposals.get(proposal_id).ok_or(Error::ProposalNotFound)?;
+            Ok(proposal.executed_by.zip(proposal.executed_at))
+        }
+
+        /// Get user's vote on a proposal
+        #[ink(message)]
+        pub fn get_user_vote(&self, proposal_id: u32, user: H160) -> Option<Vote> {
+            let stored = self.votes.get((proposal_id, user))?;
+            let proposal = self.proposals.get(proposal_id)?;
+            let option_text = proposal.voting_options.options.get(stored.option_index as usize)?.clone();
+            
+            Some(Vote {
+                voter: user,
+                choice: VoteChoice {
+                    option_index: stored.option_index as u32,
+                    option_text,
+                },
+                timestamp: stored.timestamp,
+                weight: stored.weight,
+            })
+        }
+
+        /// Get the running commitment over a proposal's ballot log, sealed once it is finalized.
+        /// Starting from 32 zero bytes, each ballot folds in as
+        /// `blake2_256(commitment ++ blake2_256(voter ++ option_index ++ weight))` in voting order.
+        /// A vote change folds in another entry for the new option with the ballot's weight, so the
+        /// commitment is recomputed from `VoteCast` and `VoteChanged` events rather than final votes.
+        #[ink(message)]
+        pub fn get_ballot_commitment(&self, proposal_id: u32) -> Result<BallotCommitment> {
+            if !self.proposals.contains(proposal_id) {
+                return Err(Error::ProposalNotFound);
+            }
+            Ok(self.ballot_commitment_of(proposal_id))
+        }
+
+        /// Get contract statistics (total, active, executed, passed, rejected proposals)
+        #[ink(message)]
+        pub fn get_stats(&self) -> (u32, u32, u32, u32, u32) {
+            let counts = &self.status_counts;
+            (self.proposal_count, counts.active, counts.executed, counts.passed, counts.rejected)
+        }
+
+        /// Recount the statuses of proposals from `range_start` up to `range_end` (exclusive), at most
+        /// `MAX_PAGE_SIZE` of them, returning the ID to continue from (owner only). A range starting
+        /// at 1 resets the counters first, so consecutive ranges from 1 rebuild them from scratch.
+        #[ink(message)]
+        pub fn rebuild_stats(&mut self, range_start: u32, range_end: u32) -> Result<u32> {
+            self.ensure_owner()?;
+            if range_start == 0 {
+                return Err(Error::InvalidProposal);
+            }
+            
+            let end = range_end.min(self.next_proposal_id)
+                .min(range_start.saturating_add(MAX_PAGE_SIZE))
+                .max(range_start);
+            if range_start == 1 {
+                self.status_counts = StatusCounts::default();
+            }
+            for proposal_id in range_start..end {
+                if let Some(proposal) = self.proposals.get(proposal_id) {
+                    self.status_counts.increment(&proposal.status);
+                }
+            }
+            
+            Ok(end)
+        }
+
+        /// Get the total number of registered voters
+        #[ink(message)]
+        pub fn get_total_voters(&self) -> u32 {
+            self.total_voters
+        }
+
+        /// Check if proposal has reached quorum
+        #[ink(message)]
+        pub fn has_reached_quorum(&self, proposal_id: u32) -> Result<bool> {
+            let current_time = self.current_time();
+            let proposal = self.proposals.get(proposal_id).ok_or(Error::ProposalNotFound)?;
+            
+            let required_votes = self.required_votes(&proposal, current_time);
+            Ok(proposal.total_voters >= required_votes)
+        }
+
+        /// Project whether a proposal would reach quorum if the given number of
+        /// additional voters took part
+        #[ink(message)]
+        pub fn quorum_projection(&self, proposal_id: u32, expected_additional_voters: u32) -> Result<bool> {
+            let current_time = self.current_time();
+            let proposal = self.proposals.get(proposal_id).ok_or(Error::ProposalNotFound)?;
+            
+            let required_votes = self.required_votes(&proposal, current_time);
+            let projected_voters = proposal.total_voters.saturating_add(expected_additional_voters);
+            Ok(projected_voters >= required_votes)
+        }
+
+        /// Get proposal results (vote counts and quorum status)
+        #[ink(message)]
+        pub fn get_proposal_results(&self, proposal_id: u32) -> Result<(Vec<u128>, bool, u32, u32)> {
+            let current_time = self.current_time();
+            let proposal = self.proposals.get(proposal_id).ok_or(Error::ProposalNotFound)?;
+            Self::check_tally_aligned(&proposal)?;
+            
+            let required_votes = self.required_votes(&proposal, current_time);
+            let has_quorum = proposal.total_voters >= required_votes;
+            
+            Ok((proposal.vote_counts, has_quorum, proposal.total_voters, required_votes))
+        }
+
+        /// Get each condition a proposal must meet to pass: (quorum, approval, winner). Approval
+        /// covers the distinct participation minimum and at least one vote being cast
+        #[ink(message)]
+        pub fn get_pass_conditions(&self, proposal_id: u32) -> Result<(bool, bool, bool)> {
+            let current_time = self.current_time();
+            let proposal = self.proposals.get(proposal_id).ok_or(Error::ProposalNotFound)?;
+            Self::check_tally_aligned(&proposal)?;
+            
+            let has_quorum = proposal.total_voters >= self.required_votes(&proposal, current_time);
+            let has_approval = proposal.total_voters >= self.min_distinct_participants
+                && proposal.decisive_counts().any(|(_, vote_count)| vote_count > 0);
+            let has_winner = self.winning_option_index(&proposal).is_some();
+            
+            Ok((has_quorum, has_approval, has_winner))
+        }
+
+        /// Get a proposal's voting duration and execution delay in whole seconds, as (voting, delay).
+        /// Unlike timestamps, which are in milliseconds, both are rounded down to the second.
+        #[ink(message)]
+        pub fn get_proposal_timing(&self, proposal_id: u32) -> Result<(u64, u64)> {
+            let proposal = self.proposals.get(proposal_id).ok_or(Error::ProposalNotFound)?;
+            let params = &proposal.governance_params;
+            Ok((
+                Self::voting_duration(params) / MILLIS_PER_SECOND,
+                Self::execution_delay(params) / MILLIS_PER_SECOND,
+            ))
+        }
+
+        /// Get voting options for a proposal
+        #[ink(message)]
+        pub fn get_voting_options(&self, proposal_id: u32) -> Result<Vec<String>> {
+            let proposal = self.proposals.get(proposal_id).ok_or(Error::ProposalNotFound)?;
+            Ok(proposal.voting_options.options)
+        }
+
+        /// Get detailed results with option names
+        #[ink(message)]
+        pub fn get_detailed_results(&self, proposal_id: u32) -> Result<Vec<(String, u128)>> {
+            let proposal = self.proposals.get(proposal_id).ok_or(Error::ProposalNotFound)?;
+            Self::check_tally_aligned(&proposal)?;
+            
+            let mut results = Vec::new();
+            for (index, &vote_count) in proposal.vote_counts.iter().enumerate() {
+                if let Some(option_text) = proposal.voting_options.options.get(index) {
+                    results.push((option_text.clone(), vote_count));
+                }
+            }
+            
+            Ok(results)
+        }
+
+        /// Get the payload attached to a voting option, if the proposal carries payloads
+        #[ink(message)]
+        pub fn get_option_payload(&self, proposal_id: u32, option_index: u32) -> Result<Option<Vec<u8>>> {
+            let proposal = self.proposals.get(proposal_id).ok_or(Error::ProposalNotFound)?;
+            
+            if option_index as usize >= proposal.voting_options.options.len() {
+                return Err(Error::InvalidProposal);
+            }
+            
+            Ok(proposal.voting_options.payloads.get(option_index as usize).cloned())
+        }
+
+        /// Get a histogram of when votes were cast as `(bucket_start, vote_count)` pairs,
+        /// with buckets aligned to multiples of `bucket_size` milliseconds and empty buckets omitted
+        #[ink(message)]
+        pub fn get_vote_timeline(&self, proposal_id: u32, bucket_size: Timestamp) -> Result<Vec<(Timestamp, u32)>> {
+            let proposal = self.proposals.get(proposal_id).ok_or(Error::ProposalNotFound)?;
+            
+            if bucket_size == 0 {
+                return Err(Error::InvalidProposal);
+            }
+            
+            let mut timeline: Vec<(Timestamp, u32)> = Vec::new();
+            for index in 0..proposal.total_voters {
+                let Some(vote) = self.proposal_voters.get((proposal_id, index))
+                    .and_then(|voter| self.votes.get((proposal_id, voter))) else {
+                    continue;
+                };
+                
+                let bucket_start = vote.timestamp - vote.timestamp % bucket_size;
+                match timeline.binary_search_by_key(&bucket_start, |&(start, _)| start) {
+                    Ok(position) => timeline[position].1 += 1,
+                    Err(position) => timeline.insert(position, (bucket_start, 1)),
+                }
+            }
+            
+            Ok(timeline)
+        }
+
+        /// Get the action executed if a voting option wins, or None if it only marks the proposal executed
+        #[ink(message)]
+        pub fn get_option_action(&self, proposal_id: u32, option_index: u32) -> Result<Option<OutcomeAction>> {
+            let proposal = self.proposals.get(proposal_id).ok_or(Error::ProposalNotFound)?;
+            
+            if option_index as usize >= proposal.voting_options.options.len() {
+                return Err(Error::InvalidProposal);
+            }
+            
+            Ok(proposal.voting_options.actions.get(option_index as usize)
+                .filter(|action| **action != OutcomeAction::NoOp)
+                .cloned())
+        }
+
+        /// Get detailed results with option names and whether each option carries an action
+        #[ink(message)]
+        pub fn get_results_with_actions(&self, proposal_id: u32) -> Result<Vec<(String, u128, bool)>> {
+            let proposal = self.proposals.get(proposal_id).ok_or(Error::ProposalNotFound)?;
+            Self::check_tally_aligned(&proposal)?;
+            
+            let mut results = Vec::new();
+            for (index, &vote_count) in proposal.vote_counts.iter().enumerate() {
+                if let Some(option_text) = proposal.voting_options.options.get(index) {
+                    let has_action = proposal.voting_options.actions.get(index)
+                        .is_some_and(|action| *action != OutcomeAction::NoOp);
+                    results.push((option_text.clone(), vote_count, has_action));
+                }
+            }
+            
+            Ok(results)
+        }
+
+        /// Get the total weight cast on a proposal, which differs from its voter count under weighting
+        #[ink(message)]
+        pub fn get_total_votes_cast_on(&self, id: u32) -> Result<u128> {
+            let proposal = self.proposals.get(id).ok_or(Error::ProposalNotFound)?;
+            Ok(proposal.vote_counts.iter().fold(0u128, |total, &vote_count| total.saturating_add(vote_count)))
+        }
+
+        /// Get the winning option and vote count
+        #[ink(message)]
+        pub fn get_winning_option(&self, proposal_id: u32) -> Result<Option<(String, u128)>> {
+            let proposal = self.proposals.get(proposal_id).ok_or(Error::ProposalNotFound)?;
+            Self::check_tally_aligned(&proposal)?;
+            
+            // Return the winning option, if there is a clear one
+            if let Some(index) = self.winning_option_index(&proposal) {
+                if let Some(option_text) = proposal.voting_options.options.get(index) {
+                    return Ok(Some((option_text.clone(), proposal.vote_counts[index])));
+                }
+            }
+            
+            Ok(None)
+        }
+
+        /// Aggregate vote counts across all proposals, keyed by normalized option label
+        #[ink(message)]
+        pub fn get_global_option_tally(&self) -> Vec<(String, u128)> {
+            let mut tally: Vec<(String, u128)> = Vec::new();
+            
+            for i in 1..self.next_proposal_id {
+                if let Some(proposal) = self.proposals.get(i) {
+                    for (option, &vote_count) in proposal.voting_options.options.iter().zip(proposal.vote_counts.iter()) {
+                        let label = option.trim().to_lowercase();
+                        match tally.iter_mut().find(|(existing, _)| *existing == label) {
+                            Some((_, total)) => *total = total.saturating_add(vote_count),
+                            None => tally.push((label, vote_count)),
+                        }
+                    }
+                }
+            }
+            
+            tally
+        }
+
+        /// Get the landing page overview in a single call, built only from maintained counters
+        #[ink(message)]
+        pub fn get_dashboard(&self) -> Dashboard {
+            Dashboard {
+                version: DASHBOARD_VERSION,
+                owner: self.owner,
+                guardian: self.guardian,
+                total_voters: self.total_voters,
+                proposal_count: self.proposal_count,
+                status_counts: self.status_counts.clone(),
+                treasury_balance: self.get_treasury_balance(),
+                tenure_weight_per_day: self.tenure_weight_per_day,
+                min_distinct_participants: self.min_distinct_participants,
+                voting_probation: self.voting_probation,
+                open_proposal_ids: self.open_proposals.clone(),
+                recent_proposal_ids: self.recent_proposals.clone(),
+            }
+        }
+
+        /// Get every owner-configurable numeric limit in a single call
+        #[ink(message)]
+        pub fn get_limits(&self) -> Limits {
+            Limits {
+                max_active_proposals: self.max_active_proposals,
+                max_voters: self.max_voters,
+                min_distinct_participants: self.min_distinct_participants,
+                tenure_weight_per_day: self.tenure_weight_per_day,
+                voting_probation: self.voting_probation,
+                max_reasonable_timestamp: self.max_reasonable_timestamp,
+                confirmation_period: self.confirmation_period,
+                min_voting_window: self.min_voting_window,
+                min_weight_to_propose: self.min_weight_to_propose,
+                max_effective_power: self.max_effective_power,
+                vote_reward: self.vote_reward,
+                vote_reward_budget: self.vote_reward_budget,
+                max_reputation_weight: self.reputation_config.max_weight,
+            }
+        }
+
+        /// Get the next proposal ID
+        #[ink(message)]
+        pub fn get_next_proposal_id(&self) -> u32 {
+            self.next_proposal_id
+        }
+
+        /// Get the outstanding keeper work, finalizations first, then executions,
+        /// then active proposals whose voting period is about to end
+        #[ink(message)]
+        pub fn get_pending_actions(&self, limit: u32) -> Vec<PendingAction> {
+            let current_time = self.current_time();
+            let limit = limit.min(MAX_PAGE_SIZE) as usize;
+            
+            let mut finalizations = Vec::new();
+            let mut executions = Vec::new();
+            let mut expiring = Vec::new();
+            
+            for &proposal_id in &self.open_proposals {
+                let Some(mut proposal) = self.proposals.get(proposal_id) else {
+                    continue;
+                };
+                Self::activate_if_due(&mut proposal, current_time);
+                match proposal.status {
+                    ProposalStatus::Active if current_time > proposal.voting_end => {
+                        finalizations.push(PendingAction::NeedsFinalization(proposal_id));
+                    }
+                    ProposalStatus::Active if proposal.voting_end - current_time <= EXPIRING_SOON_WINDOW => {
+                        expiring.push(PendingAction::ExpiringSoon {
+                            proposal_id,
+                            deadline: proposal.voting_end,
+                        });
+                    }
+                    ProposalStatus::Passed if current_time >= proposal.execution_time
+                        && !Self::execution_window_closed(&proposal, current_time) => {
+                        executions.push(PendingAction::ReadyToExecute(proposal_id));
+                    }
+                    _ => {}
+                }
+            }
+            
+            finalizations.into_iter()
+                .chain(executions)
+                .chain(expiring)
+                .take(limit)
+                .collect()
+        }
+
+        /// Set the allowed voting period and execution delay range for a proposal type (owner only).
+        /// Proposals already created are unaffected.
+        #[ink(message)]
+        pub fn set_duration_bounds(&mut self, proposal_type: ProposalType, bounds: DurationBounds) -> Result<()> {
+            self.ensure_owner()?;
+            
+            if bounds.min_voting > bounds.max_voting {
+                return Err(Error::InvalidProposal);
+            }
+            
+            self.duration_bounds.insert(proposal_type, &bounds);
+            Ok(())
+        }
+
+        /// Get the allowed duration range for a proposal type
+        #[ink(message)]
+        pub fn get_duration_bounds(&self, proposal_type: ProposalType) -> DurationBounds {
+            self.duration_bounds.get(proposal_type).unwrap_or_default()
+        }
+
+        /// Set the minimum title and description lengths for a proposal type (owner only).
+        /// Proposals already created are unaffected.
+        #[ink(message)]
+        pub fn set_length_minimums(&mut self, proposal_type: ProposalType, minimums: LengthMinimums) -> Result<()> {
+            self.ensure_owner()?;
+            
+            // Minimums above the maximums would block the type entirely
+            if minimums.min_title as usize > MAX_TITLE_LENGTH || minimums.min_description as usize > MAX_DESCRIPTION_LENGTH {
+                return Err(Error::InvalidProposal);
+            }
+            
+            self.length_minimums.insert(proposal_type, &minimums);
+            Ok(())
+        }
+
+        /// Get the minimum title and description lengths for a proposal type
+        #[ink(message)]
+        pub fn get_length_minimums(&self, proposal_type: ProposalType) -> LengthMinimums {
+            self.length_minimums.get(proposal_type).unwrap_or_default()
+        }
+
+        /// Pause or unpause proposal creation, voting and execution (owner only)
+        #[ink(message)]
+        pub fn set_paused(&mut self, paused: bool) -> Result<()> {
+            self.ensure_owner()?;
+            self.paused = paused;
+            Ok(())
+        }
+
+        /// Check whether the contract is paused
+        #[ink(message)]
+        pub fn is_paused(&self) -> bool {
+            self.paused
+        }
+
+        /// Set the maximum number of proposals open for voting at once, 0 for no limit (owner only)
+        #[ink(message)]
+        pub fn set_max_active_proposals(&mut self, max_active: u32) -> Result<()> {
+            self.ensure_owner()?;
+            self.max_active_proposals = max_active;
+            Ok(())
+        }
+
+        /// Get the maximum number of proposals open for voting at once
+        #[ink(message)]
+        pub fn get_max_active_proposals(&self) -> u32 {
+            self.max_active_proposals
+        }
+
+        /// Set the maximum number of registered voters, 0 for no limit (owner only)
+        #[ink(message)]
+        pub fn set_max_voters(&mut self, max_voters: u32) -> Result<()> {
+            self.ensure_owner()?;
+            self.max_voters = max_voters;
+            Ok(())
+        }
+
+        /// Get the maximum number of registered voters
+        #[ink(message)]
+        pub fn get_max_voters(&self) -> u32 {
+            self.max_voters
+        }
+
+        /// Nominate a new owner, who takes over once they call `accept_ownership` (owner only).
+        /// A later nomination replaces an earlier one.
+        #[ink(message)]
+        pub fn transfer_ownership(&mut self, new_owner: H160) -> Result<()> {
+            self.ensure_owner()?;
+            self.pending_owner = Some(new_owner);
+            Ok(())
+        }
+
+        /// Become the owner as the nominated pending owner
+        #[ink(message)]
+        pub fn accept_ownership(&mut self) -> Result<()> {
+            let caller = self.env().caller();
+            if self.pending_owner != Some(caller) {
+                return Err(Error::NotAuthorized);
+            }
+            
+            let old = self.owner;
+            self.owner = caller;
+            self.pending_owner = None;
+            
+            self.env().emit_event(OwnershipTransferred {
+                old,
+                new: caller,
+            });
+            
+            Ok(())
+        }
+
+        /// Get the owner account
+        #[ink(message)]
+        pub fn get_owner(&self) -> H160 {
+            self.owner
+        }
+
+        /// Get the account nominated to become owner, if any
+        #[ink(message)]
+        pub fn get_pending_owner(&self) -> Option<H160> {
+            self.pending_owner
+        }
+
+        /// Appoint the account allowed to suspend and resume proposals (owner only)
+        #[ink(message)]
+        pub fn set_guardian(&mut self, guardian: H160) -> Result<()> {
+            self.ensure_owner()?;
+            self.guardian = guardian;
+            Ok(())
+        }
+
+        /// Get the guardian account
+        #[ink(message)]
+        pub fn get_guardian(&self) -> H160 {
+            self.guardian
+        }
+
+        /// Set the extra voting weight granted per day of membership (owner only)
+        #[ink(message)]
+        pub fn set_tenure_weight_per_day(&mut self, weight: u128) -> Result<()> {
+            self.ensure_owner()?;
+            self.tenure_weight_per_day = weight;
+            Ok(())
+        }
+
+        /// Get the extra voting weight granted per day of membership
+        #[ink(message)]
+        pub fn get_tenure_weight_per_day(&self) -> u128 {
+            self.tenure_weight_per_day
+        }
+
+        /// Set the minimum number of distinct voters a proposal needs to pass (owner only)
+        #[ink(message)]
+        pub fn set_min_distinct_participants(&mut self, min_participants: u32) -> Result<()> {
+            self.ensure_owner()?;
+            self.min_distinct_participants = min_participants;
+            Ok(())
+        }
+
+        /// Get the minimum number of distinct voters a proposal needs to pass
+        #[ink(message)]
+        pub fn get_min_distinct_participants(&self) -> u32 {
+            self.min_distinct_participants
+        }
+
+        /// Configure the probation period for new voters and whether probationary
+        /// voters count toward the quorum denominator (owner only)
+        #[ink(message)]
+        pub fn set_voting_probation(&mut self, probation: Timestamp, counts_toward_quorum: bool) -> Result<()> {
+            self.ensure_owner()?;
+            self.voting_probation = probation;
+            self.probation_counts_toward_quorum = counts_toward_quorum;
+            Ok(())
+        }
+
+        /// Get the probation period and whether probationary voters count toward quorum
+        #[ink(message)]
+        pub fn get_voting_probation(&self) -> (Timestamp, bool) {
+            (self.voting_probation, self.probation_counts_toward_quorum)
+        }
+
+        /// Get the lowest quorum threshold new proposals may use
+        #[ink(message)]
+        pub fn get_default_quorum_threshold(&self) -> QuorumThreshold {
+            self.default_quorum_threshold.clone()
+        }
+
+        /// Set the latest block timestamp at which proposals may still be created (owner only)
+        #[ink(message)]
+        pub fn set_max_reasonable_timestamp(&mut self, timestamp: Timestamp) -> Result<()> {
+            self.ensure_owner()?;
+            self.max_reasonable_timestamp = timestamp;
+            Ok(())
+        }
+
+        /// Get the latest block timestamp at which proposals may still be created
+        #[ink(message)]
+        pub fn get_max_reasonable_timestamp(&self) -> Timestamp {
+            self.max_reasonable_timestamp
+        }
+
+        /// Set how long a proposal must keep meeting its thresholds before passing (owner only, 0 disables)
+        #[ink(message)]
+        pub fn set_confirmation_period(&mut self, period: Timestamp) -> Result<()> {
+            self.ensure_owner()?;
+            self.confirmation_period = period;
+            Ok(())
+        }
+
+        /// Get the confirmation period in milliseconds
+        #[ink(message)]
+        pub fn get_confirmation_period(&self) -> Timestamp {
+            self.confirmation_period
+        }
+
+        /// Set the grace period given to new proposals that do not set their own (owner only).
+        /// It cannot be 0, as passed proposals would then expire the moment they become executable.
+        /// Proposals already created are unaffected.
+        #[ink(message)]
+        pub fn set_default_grace_period(&mut self, grace_period: Timestamp) -> Result<()> {
+            self.ensure_owner()?;
+            if grace_period == 0 {
+                return Err(Error::InvalidProposal);
+            }
+            self.default_grace_period = grace_period;
+            Ok(())
+        }
+
+        /// Get the default grace period in milliseconds
+        #[ink(message)]
+        pub fn get_default_grace_period(&self) -> Timestamp {
+            self.default_grace_period
+        }
+
+        /// Allow or forbid contract accounts from registering themselves as voters (owner only)
+        #[ink(message)]
+        pub fn set_allow_contract_voters(&mut self, allow: bool) -> Result<()> {
+            self.ensure_owner()?;
+            self.allow_contract_voters = allow;
+            Ok(())
+        }
+
+        /// Get whether contract accounts may register themselves as voters
+        #[ink(message)]
+        pub fn get_allow_contract_voters(&self) -> bool {
+            self.allow_contract_voters
+        }
+
+        /// Choose where voting weight is derived from (owner only)
+        #[ink(message)]
+        pub fn set_weight_source(&mut self, source: WeightSource) -> Result<()> {
+            self.ensure_owner()?;
+            self.weight_source = source;
+            Ok(())
+        }
+
+        /// Get where voting weight is derived from
+        #[ink(message)]
+        pub fn get_weight_source(&self) -> WeightSource {
+            self.weight_source.clone()
+        }
+
+        /// Set how ties between the leading options are resolved (owner only)
+        #[ink(message)]
+        pub fn set_tie_break_policy(&mut self, policy: TieBreakPolicy) -> Result<()> {
+            self.ensure_owner()?;
+            self.tie_break_policy = policy;
+            Ok(())
+        }
+
+        /// Get how ties between the leading options are resolved
+        #[ink(message)]
+        pub fn get_tie_break_policy(&self) -> TieBreakPolicy {
+            self.tie_break_policy.clone()
+        }
+
+        /// Set when the quorum denominator is counted: at proposal creation or at finalization (owner only)
+        #[ink(message)]
+        pub fn set_quorum_timing(&mut self, timing: QuorumTiming) -> Result<()> {
+            self.ensure_owner()?;
+            self.quorum_timing = timing;
+            Ok(())
+        }
+
+        /// Get when the quorum denominator is counted
+        #[ink(message)]
+        pub fn get_quorum_timing(&self) -> QuorumTiming {
+            self.quorum_timing.clone()
+        }
+
+        /// Set reputation accrual rates and weight conversion (owner only)
+        #[ink(message)]
+        pub fn set_reputation_config(&mut self, config: ReputationConfig) -> Result<()> {
+            self.ensure_owner()?;
+            if config.divisor == 0 {
+                return Err(Error::InvalidProposal);
+            }
+            self.reputation_config = config;
+            Ok(())
+        }
+
+        /// Get reputation accrual rates and weight conversion
+        #[ink(message)]
+        pub fn get_reputation_config(&self) -> ReputationConfig {
+            self.reputation_config.clone()
+        }
+
+        /// Set the minimum milliseconds between proposal creation and the voting deadline (owner only)
+        #[ink(message)]
+        pub fn set_min_voting_window(&mut self, window: Timestamp) -> Result<()> {
+            self.ensure_owner()?;
+            self.min_voting_window = window;
+            Ok(())
+        }
+
+        /// Get the minimum milliseconds between proposal creation and the voting deadline
+        #[ink(message)]
+        pub fn get_min_voting_window(&self) -> Timestamp {
+            self.min_voting_window
+        }
+
+        /// Set the Merkle root of eligible addresses used by proposals created from now on,
+        /// or None to fall back to voter registration (owner only)
+        #[ink(message)]
+        pub fn set_eligibility_root(&mut self, eligibility: Option<EligibilityRoot>) -> Result<()> {
+            self.ensure_owner()?;
+            self.eligibility = eligibility;
+            Ok(())
+        }
+
+        /// Get the eligibility root new proposals will be pinned to
+        #[ink(message)]
+        pub fn get_eligibility_root(&self) -> Option<EligibilityRoot> {
+            self.eligibility.clone()
+        }
+
+        /// Get the eligibility root a proposal was pinned to at creation
+        #[ink(message)]
+        pub fn get_proposal_eligibility(&self, proposal_id: u32) -> Option<EligibilityRoot> {
+            self.proposal_eligibility.get(proposal_id)
+        }
+
+        /// Set the per-vote reward and the per-proposal reward budget (owner only, 0 reward disables)
+        #[ink(message)]
+        pub fn set_vote_reward(&mut self, reward: u128, budget_per_proposal: u128) -> Result<()> {
+            self.ensure_owner()?;
+            self.vote_reward = reward;
+            self.vote_reward_budget = budget_per_proposal;
+            Ok(())
+        }
+
+        /// Get the per-vote reward and the per-proposal reward budget
+        #[ink(message)]
+        pub fn get_vote_reward(&self) -> (u128, u128) {
+            (self.vote_reward, self.vote_reward_budget)
+        }
+
+        /// Get the total vote rewards paid out on a proposal
+        #[ink(message)]
+        pub fn get_rewards_distributed(&self, proposal_id: u32) -> u128 {
+            self.rewards_distributed.get(proposal_id).unwrap_or(0)
+        }
+
+        /// Enable or disable weight slashing for voters who miss consecutive proposals (owner only)
+        #[ink(message)]
+        pub fn set_absence_slashing(&mut self, slashing: Option<AbsenceSlashing>) -> Result<()> {
+            self.ensure_owner()?;
+            if let Some(config) = &slashing {
+                if config.miss_threshold == 0 || config.retained_percent > 100 {
+                    return Err(Error::InvalidProposal);
+                }
+            }
+            self.absence_slashing = slashing;
+            Ok(())
+        }
+
+        /// Get the absence slashing configuration
+        #[ink(message)]
+        pub fn get_absence_slashing(&self) -> Option<AbsenceSlashing> {
+            self.absence_slashing.clone()
+        }
+
+        /// Get how many consecutive finalized proposals a voter has missed while slashing was enabled
+        #[ink(message)]
+        pub fn get_missed_proposals(&self, voter: H160) -> u32 {
+            self.missed_proposals(voter)
+        }
+
+        /// Set the voting weight a proposer must hold to create a proposal (owner only, 0 disables)
+        #[ink(message)]
+        pub fn set_min_weight_to_propose(&mut self, weight: u128) -> Result<()> {
+            self.ensure_owner()?;
+            self.min_weight_to_propose = weight;
+            Ok(())
+        }
+
+        /// Get the voting weight a proposer must hold to create a proposal
+        #[ink(message)]
+        pub fn get_min_weight_to_propose(&self) -> u128 {
+            self.min_weight_to_propose
+        }
+
+        /// Assign a voter's base voting weight, which tenure or reputation bonuses add to (owner only)
+        #[ink(message)]
+        pub fn assign_voting_weight(&mut self, voter: H160, weight: u128) -> Result<()> {
+            self.ensure_owner()?;
+            self.assigned_weights.insert(voter, &weight);
+            Ok(())
+        }
+
+        /// Get a voter's assigned base voting weight
+        #[ink(message)]
+        pub fn get_assigned_weight(&self, voter: H160) -> u128 {
+            self.assigned_weights.get(voter).unwrap_or(1)
+        }
+
+        /// Set the most voting weight a single ballot may carry (owner only, 0 removes the cap)
+        #[ink(message)]
+        pub fn set_max_effective_power(&mut self, max_power: u128) -> Result<()> {
+            self.ensure_owner()?;
+            self.max_effective_power = max_power;
+            Ok(())
+        }
+
+        /// Get the most voting weight a single ballot may carry
+        #[ink(message)]
+        pub fn get_max_effective_power(&self) -> u128 {
+            self.max_effective_power
+        }
+
+        /// Set whether treasury proposals must carry at least one transfer action (owner only)
+        #[ink(message)]
+        pub fn set_require_treasury_actions(&mut self, required: bool) -> Result<()> {
+            self.ensure_owner()?;
+            self.require_treasury_actions = required;
+            Ok(())
+        }
+
+        /// Get whether treasury proposals must carry at least one transfer action
+        #[ink(message)]
+        pub fn get_require_treasury_actions(&self) -> bool {
+            self.require_treasury_actions
+        }
+
+        /// Get the weight an account's ballot would carry right now, after slashing and the power cap
+        #[ink(message)]
+        pub fn get_voting_power(&self, account: H160) -> Result<u128> {
+            let current_time = self.current_time();
+            self.voting_weight(account, current_time)
+        }
+
+        /// Set the number of flags that automatically suspends a proposal (owner only, None disables)
+        #[ink(message)]
+        pub fn set_flag_threshold(&mut self, threshold: Option<FlagThreshold>) -> Result<()> {
+            self.ensure_owner()?;
+            self.flag_threshold = threshold;
+            Ok(())
+        }
+
+        /// Get the number of flags that automatically suspends a proposal
+        #[ink(message)]
+        pub fn get_flag_threshold(&self) -> Option<FlagThreshold> {
+            self.flag_threshold.clone()
+        }
+
+        /// Get the governance halt currently blocking new proposals, if any
+        #[ink(message)]
+        pub fn get_governance_halt(&self) -> Option<GovernanceHalt> {
+            self.active_halt(self.current_time())
+        }
+
+        /// Restore every parameter whose sunset has passed, returning how many were restored
+        #[ink(message)]
+        pub fn apply_sunsets(&mut self) -> u32 {
+            let current_time = self.current_time();
+            let (due, pending): (Vec<PendingSunset>, Vec<PendingSunset>) = core::mem::take(&mut self.pending_sunsets)
+                .into_iter()
+                .partition(|sunset| sunset.revert_at <= current_time);
+            self.pending_sunsets = pending;
+            
+            for sunset in due.iter() {
+                self.apply_governance_action(&sunset.revert);
+                self.env().emit_event(ParameterSunset {
+                    action: sunset.revert.clone(),
+                });
+            }
+            due.len() as u32
+        }
+
+        /// Get the parameter reverts still waiting for their sunset
+        #[ink(message)]
+        pub fn get_pending_sunsets(&self) -> Vec<PendingSunset> {
+            self.pending_sunsets.clone()
+        }
+
+        /// Get the reputation points an account has earned
+        #[ink(message)]
+        pub fn get_reputation(&self, account: H160) -> u128 {
+            self.reputation.get(account).unwrap_or(0)
+        }
+
+        /// Get whether an active proposal is still deciding or already confirming
+        #[ink(message)]
+        pub fn get_decision_state(&self, proposal_id: u32) -> Result<DecisionState> {
+            if !self.proposals.contains(proposal_id) {
+                return Err(Error::ProposalNotFound);
+            }
+            Ok(self.decision_states.get(proposal_id).unwrap_or(DecisionState::Deciding))
+        }
+
+        /// Get the timestamp at which a voter registered
+        #[ink(message)]
+        pub fn get_registration_time(&self, user: H160) -> Option<Timestamp> {
+            self.voter_info_of(user).map(|info| info.registered_at)
+        }
+    }
+
+    impl TreasuryGovernance {
+        /// Current block timestamp in milliseconds
+        fn current_time(&self) -> Timestamp {
+            self.env().block_timestamp()
+        }
+
+        /// Native balance held by the contract
+        fn contract_balance(&self) -> u128 {
+            self.env().balance().try_into().unwrap_or(u128::MAX)
+        }
+
+        /// Add to an account's stake and the staked total
+        fn add_stake(&mut self, account: H160, amount: u128) -> Result<()> {
+            if amount == 0 {
+                return Ok(());
+            }
+            let stake = self.get_stake(account).checked_add(amount).ok_or(Error::ArithmeticOverflow)?;
+            self.total_weight = self.total_weight.checked_add(amount).ok_or(Error::ArithmeticOverflow)?;
+            self.stakes.insert(account, &stake);
+            Ok(())
+        }
+
+        /// Record a new voter registration
+        fn register(&mut self, account: H160) -> Result<()> {
+            // Check if user is already registered
+            if self.is_voter_registered(account) {
+                return Err(Error::AlreadyRegistered);
+            }
+            
+            // Permissioned DAOs may fix their membership size
+            if self.max_voters > 0 && self.total_voters >= self.max_voters {
+                return Err(Error::VoterCapReached);
+            }
+            
+            let registered_at = self.current_time();
+            
+            // Register the voter globally; proposals finalized before now are not missed
+            self.voter_info.insert(account, &VoterInfo {
+                registered_at,
+                registered_by: self.env().caller(),
+                tier: VoterTier::Member,
+                votes_cast: 0,
+                proposals_created: 0,
+                last_active_at: registered_at,
+            });
+            self.participation_marks.insert(account, &self.finalized_proposals);
+            
+            // Track the registration while it can still be inside the probation window
+            let probation = self.voting_probation;
+            self.recent_registrations.retain(|&timestamp| registered_at < timestamp.saturating_add(probation));
+            if probation > 0 {
+                self.recent_registrations.push(registered_at);
+            }
+            
+            // Increment total voter count
+            self.total_voters += 1;
+            
+            self.env().emit_event(VoterRegistered { voter: account });
+            
+            Ok(())
+        }
+
+        /// Ensure the external condition attached to a proposal holds right now
+        fn check_oracle_condition(&self, proposal: &Proposal) -> Result<()> {
+            match &proposal.governance_params.oracle {
+                Some(condition) if !self.query_oracle(condition)? => Err(Error::OracleConditionNotMet),
+                _ => Ok(()),
+            }
+        }
+
+        /// Ask an oracle contract whether its condition holds
+        #[cfg(not(test))]
+        fn query_oracle(&self, condition: &OracleCondition) -> Result<bool> {
+            ink::env::call::build_call::<Environment>()
+                .call(condition.oracle)
+                .exec_input(ink::env::call::ExecutionInput::new(ink::env::call::Selector::new(condition.selector)))
+                .returns::<bool>()
+                .try_invoke()
+                .map_err(|_| Error::ExecutionFailed)?
+                .map_err(|_| Error::ExecutionFailed)
+        }
+
+        /// Ask an oracle contract whether its condition holds, as answered by the test double
+        #[cfg(test)]
+        fn query_oracle(&self, condition: &OracleCondition) -> Result<bool> {
+            super::oracle_responses::response(condition.oracle).ok_or(Error::ExecutionFailed)
+        }
+
+        /// Whether an account has contract code deployed
+        #[cfg(not(test))]
+        fn is_contract_account(&self, account: H160) -> bool {
+            self.env().is_contract(&account)
+        }
+
+        /// Whether an account has contract code deployed, as marked by the test double
+        #[cfg(test)]
+        fn is_contract_account(&self, account: H160) -> bool {
+            super::contract_accounts::is_contract(account)
+        }
+
+        /// Ensure the caller is the contract owner
+        fn ensure_owner(&self) -> Result<()> {
+            if self.env().caller() != self.owner {
+                return Err(Error::NotAuthorized);
+            }
+            Ok(())
+        }
+
+        /// Run every creation-time check and return the resulting (voting_end, execution_time)
+        fn validate_proposal(&self, title: &str, description: &str, proposal_type: &ProposalType, voting_options: &VotingOptions, governance_params: &GovernanceParameters, current_time: Timestamp) -> Result<(Timestamp, Timestamp)> {
+            // Validate title and description lengths
+            if title.trim().is_empty() || title.len() > MAX_TITLE_LENGTH || description.len() > MAX_DESCRIPTION_LENGTH {
+                return Err(Error::InvalidProposal);
+            }
+            let minimums = self.get_length_minimums(proposal_type.clone());
+            if title.len() < minimums.min_title as usize || description.len() < minimums.min_description as usize {
+                return Err(Error::InvalidProposal);
+            }
+            
+            // Validate voting options
+            if voting_options.options.is_empty() || voting_options.options.len() > 10 {
+                return Err(Error::InvalidProposal);
+            }
+           
+            // Validate that all voting options are non-empty and distinct
+            for (index, option) in voting_options.options.iter().enumerate() {
+                if option.trim().is_empty() {
+                    return Err(Error::InvalidProposal);
+                }
+                if voting_options.options[..index].iter().any(|previous| previous.trim() == option.trim()) {
+                    return Err(Error::InvalidProposal);
+                }
+            }
+            
+            // An abstain option needs at least one other option that can win
+            if let Some(abstain_index) = voting_options.abstain_index {
+                if abstain_index as usize >= voting_options.options.len() || voting_options.options.len() < 2 {
+                    return Err(Error::InvalidProposal);
+                }
+            }
+            
+            // Validate option payloads, which are opaque bytes bounded in size
+            if !voting_options.payloads.is_empty() {
+                if voting_options.payloads.len() != voting_options.options.len() {
+                    return Err(Error::InvalidProposal);
+                }
+                if voting_options.payloads.iter().any(|payload| payload.len() > MAX_OPTION_PAYLOAD_SIZE) {
+                    return Err(Error::InvalidProposal);
+                }
+            }
+            
+            // Validate option actions, one per option when present
+            if !voting_options.actions.is_empty() {
+                if voting_options.actions.len() != voting_options.options.len() {
+                    return Err(Error::InvalidProposal);
+                }
+                if !voting_options.actions.iter().all(Self::is_valid_action) {
+                    return Err(Error::InvalidProposal);
+                }
+            }
+            
+            // Treasury transfers belong on treasury proposals, which may be required to carry one
+            let has_transfer = voting_options.actions.iter()
+                .any(|action| matches!(action, OutcomeAction::Transfer { .. }));
+            if has_transfer && *proposal_type != ProposalType::Treasury {
+                return Err(Error::InvalidProposal);
+            }
+            if self.require_treasury_actions && *proposal_type == ProposalType::Treasury && !has_transfer {
+                return Err(Error::InvalidProposal);
+            }
+            
+            // Governance proposals must carry well-formed parameter changes in their payloads
+            if *proposal_type == ProposalType::Governance {
+                for payload in voting_options.payloads.iter().filter(|payload| !payload.is_empty()) {
+                    let change = <ParameterChange as scale::DecodeAll>::decode_all(&mut payload.as_slice())
+                        .map_err(|_| Error::InvalidProposal)?;
+                    // A sunset needs a positive duration and a previous value that can be restored
+                    if let Some(sunset_after) = change.sunset_after {
+                        if sunset_after == 0 || self.current_setting(&change.action).is_none() {
+                            return Err(Error::InvalidProposal);
+                        }
+                    }
+                }
+            }
+            
+            // A designated voter subset must be non-empty, bounded and free of duplicates
+            if let Some(eligible_voters) = &governance_params.eligible_voters {
+                if eligible_voters.is_empty() || eligible_voters.len() > MAX_ELIGIBLE_VOTERS {
+                    return Err(Error::InvalidProposal);
+                }
+                for (index, voter) in eligible_voters.iter().enumerate() {
+                    if eligible_voters[..index].contains(voter) {
+                        return Err(Error::InvalidProposal);
+                    }
+                }
+            }
+            
+            // An oracle condition needs a contract to ask
+            if governance_params.oracle.as_ref().is_some_and(|condition| condition.oracle == H160::zero()) {
+                return Err(Error::InvalidProposal);
+            }
+            
+            // Calculate voting end and execution times based on governance parameters
+            let voting_duration = Self::voting_duration(governance_params);
+            if governance_params.execution_delay_override.is_some_and(|delay| delay > MAX_EXECUTION_DELAY) {
+                return Err(Error::InvalidProposal);
+            }
+            let execution_delay = Self::execution_delay(governance_params);
+            
+            // Enforce the duration bounds configured for this proposal type
+            let bounds = self.get_duration_bounds(proposal_type.clone());
+            if voting_duration < bounds.min_voting {
+                return Err(Error::VotingPeriodTooShort);
+            }
+            if voting_duration > bounds.max_voting {
+                return Err(Error::VotingPeriodTooLong);
+            }
+            if execution_delay < bounds.min_delay {
+                return Err(Error::ExecutionDelayTooShort);
+            }
+            
+            // Voting opens at the scheduled activation time, if one lies in the future
+            let voting_start = match governance_params.scheduled_activation {
+                Some(activation) if activation > current_time => activation,
+                _ => current_time,
+            };
+            
+            let voting_end = voting_start.checked_add(voting_duration)
+                .ok_or(Error::InvalidProposal)?;
+            
+            // Voting must stay open for at least the minimum window after creation
+            if voting_end <= current_time || voting_end - current_time < self.min_voting_window {
+                return Err(Error::InvalidProposal);
+            }
+            
+            let execution_time = voting_end.checked_add(execution_delay)
+                .ok_or(Error::InvalidProposal)?;
+            
+            Ok((voting_end, execution_time))
+        }
+
+        /// Length of the voting period the parameters select
+        fn voting_duration(governance_params: &GovernanceParameters) -> Timestamp {
+            match governance_params.voting_period {
+                VotingPeriod::ThreeDays => 3 * MILLIS_PER_DAY,
+                VotingPeriod::SevenDays => 7 * MILLIS_PER_DAY,
+                VotingPeriod::FourteenDays => 14 * MILLIS_PER_DAY,
+                VotingPeriod::ThirtyDays => 30 * MILLIS_PER_DAY,
+            }
+        }
+
+        /// Delay between the end of voting and execution, preferring a custom override
+        fn execution_delay(governance_params: &GovernanceParameters) -> Timestamp {
+            match governance_params.execution_delay_override {
+                Some(delay) => delay,
+                None => match governance_params.execution_delay {
+                    ExecutionDelay::Immediately => 0,
+                    ExecutionDelay::OneDay => MILLIS_PER_DAY,
+                    ExecutionDelay::TwoDays => 2 * MILLIS_PER_DAY,
+                    ExecutionDelay::SevenDays => 7 * MILLIS_PER_DAY,
+                },
+            }
+        }
+
+        /// Ensure the contract is not paused
+        fn ensure_not_paused(&self) -> Result<()> {
+            if self.paused {
+                return Err(Error::ContractPaused);
+            }
+            Ok(())
+        }
+
+        /// Contract-level conditions that gate proposal creation
+        fn check_can_create(&self, proposer: H160, current_time: Timestamp) -> Result<()> {
+            self.ensure_not_paused()?;
+            
+            // A community-voted halt blocks new proposals while in-flight ones finish
+            if self.active_halt(current_time).is_some() {
+                return Err(Error::GovernanceHalted);
+            }
+            
+            // Reject creation when the block clock looks implausible
+            if current_time > self.max_reasonable_timestamp {
+                return Err(Error::InvalidProposal);
+            }
+            
+            // Limit how many proposals can be open for voting at once
+            if self.max_active_proposals > 0 {
+                let counts = &self.status_counts;
+                let active = counts.scheduled + counts.active + counts.suspended;
+                if active >= self.max_active_proposals {
+                    return Err(Error::TooManyActiveProposals);
+                }
+            }
+            
+            // Only stakeholders with enough voting weight may propose
+            if self.min_weight_to_propose > 0 && self.voting_weight(proposer, current_time)? < self.min_weight_to_propose {
+                return Err(Error::InsufficientVotingPower);
+            }
+            
+            Ok(())
+        }
+
+        /// Registration record of a voter, reading legacy entries as `VoterInfo` until upgraded
+        fn voter_info_of(&self, account: H160) -> Option<VoterInfo> {
+            if let Some(info) = self.voter_info.get(account) {
+                return Some(info);
+            }
+            if !self.registered_voters.get(account).unwrap_or(false) {
+                return None;
+            }
+            
+            // Legacy entries recorded neither who registered the voter nor any activity
+            let registered_at = self.voter_registered_at.get(account).unwrap_or(0);
+            Some(VoterInfo {
+                registered_at,
+                registered_by: account,
+                tier: VoterTier::Member,
+                votes_cast: 0,
+                proposals_created: 0,
+                last_active_at: registered_at,
+            })
+        }
+
+        /// Write a voter's record, dropping any legacy entries it supersedes
+        fn store_voter_info(&mut self, account: H160, info: &VoterInfo) {
+            self.voter_info.insert(account, info);
+            self.registered_voters.remove(account);
+            self.voter_registered_at.remove(account);
+        }
+
+        /// Write a registration in the format used before `VoterInfo`, as a deployed instance would hold it
+        #[cfg(test)]
+        pub fn insert_legacy_voter_for_test(&mut self, account: H160, registered_at: Timestamp) {
+            self.registered_voters.insert(account, &true);
+            self.voter_registered_at.insert(account, &registered_at);
+            self.total_voters += 1;
+        }
+
+        /// Zero the status counters, as a deployment from before they were maintained would hold them
+        #[cfg(test)]
+        pub fn clear_status_counts_for_test(&mut self) {
+            self.status_counts = StatusCounts::default();
+        }
+
+        /// Overwrite a proposal's stored tally, simulating a desynchronization bug in tests
+        #[cfg(test)]
+        pub fn corrupt_tally_for_test(&mut self, proposal_id: u32, vote_counts: Vec<u128>) {
+            if let Some(mut proposal) = self.proposals.get(proposal_id) {
+                proposal.vote_counts = vote_counts;
+                self.proposals.insert(proposal_id, &proposal);
+            }
+        }
+
+        /// Ensure a proposal's tally has one count per voting option, so counts map to the right labels
+        fn check_tally_aligned(proposal: &Proposal) -> Result<()> {
+            if proposal.vote_counts.len() != proposal.voting_options.options.len() {
+                return Err(Error::InvalidProposal);
+            }
+            Ok(())
+        }
+
+        /// Ensure a proposal has passed and its execution time has been reached
+        fn check_can_execute(proposal: &Proposal, current_time: Timestamp) -> Result<()> {
+            if proposal.status == ProposalStatus::Expired {
+                return Err(Error::ProposalExpired);
+            }
+            if proposal.status == ProposalStatus::Cancelled {
+                return Err(Error::ProposalNotActive);
+            }
+            
+            // Check if proposal is passed and ready for execution
+            if proposal.status != ProposalStatus::Passed {
+                return Err(Error::ProposalNotReadyForExecution);
+            }
+            
+            // Check if execution time has been reached
+            if current_time < proposal.execution_time {
+                return Err(Error::ProposalNotReadyForExecution);
+            }
+            
+            if Self::execution_window_closed(proposal, current_time) {
+                return Err(Error::ProposalExpired);
+            }
+            
+            Ok(())
+        }
+
+        /// Whether the grace period for executing a proposal has run out
+        fn execution_window_closed(proposal: &Proposal, current_time: Timestamp) -> bool {
+            proposal.governance_params.grace_period
+                .is_some_and(|grace_period| current_time > proposal.execution_time.saturating_add(grace_period))
+        }
+
+        /// The governance halt in force at the given time, if it has not expired
+        fn active_halt(&self, current_time: Timestamp) -> Option<GovernanceHalt> {
+            self.governance_halt.clone()
+                .filter(|halt| halt.until.is_none_or(|until| current_time < until))
+        }
+
+        /// Ensure the caller is the guardian
+        fn ensure_guardian(&self) -> Result<()> {
+            if self.env().caller() != self.guardian {
+                return Err(Error::NotAuthorized);
+            }
+            Ok(())
+        }
+
+        /// Persist a proposal, keeping the status counters and open index in sync
+        fn store_proposal(&mut self, proposal: &Proposal) {
+            let previous_status = self.proposals.get(proposal.id).map(|stored| stored.status);
+            
+            if previous_status.as_ref() != Some(&proposal.status) {
+                if let Some(previous) = previous_status {
+                    self.status_counts.decrement(&previous);
+                }
+                self.status_counts.increment(&proposal.status);
+                
+                if proposal.status.is_terminal() {
+                    self.close_proposal(proposal.id);
+                }
+            }
+            
+            self.proposals.insert(proposal.id, proposal);
+        }
+
+        /// Drop a proposal that reached a terminal status from the open index
+        fn close_proposal(&mut self, proposal_id: u32) {
+            if let Some(position) = self.open_proposals.iter().position(|&id| id == proposal_id) {
+                self.open_proposals.swap_remove(position);
+            }
+            self.decision_states.remove(proposal_id);
+        }
+
+        /// Whether an active proposal currently meets quorum, participation and has a clear winner
+        fn meets_pass_conditions(&self, proposal: &Proposal, current_time: Timestamp) -> bool {
+            self.decide_outcome(proposal, current_time).status == ProposalStatus::Passed
+        }
+
+        /// Decide the outcome of an active proposal from its current tallies: quorum,
+        /// distinct participation, then a clear winner must all hold for it to pass
+        fn decide_outcome(&self, proposal: &Proposal, current_time: Timestamp) -> SimulatedOutcome {
+            let winning_option = self.winning_option_index(proposal).map(|index| index as u32);
+            let has_votes = proposal.decisive_counts().any(|(_, vote_count)| vote_count > 0);
+            
+            let failing_check = if proposal.total_voters < self.required_votes(proposal, current_time) {
+                Some(FinalizationCheck::Quorum)
+            } else if proposal.total_voters < self.min_distinct_participants {
+                Some(FinalizationCheck::Participation)
+            } else if !has_votes {
+                Some(FinalizationCheck::NoVotes)
+            } else if winning_option.is_none() {
+                Some(FinalizationCheck::Tie)
+            } else {
+                None
+            };
+            
+            SimulatedOutcome {
+                status: if failing_check.is_none() { ProposalStatus::Passed } else { ProposalStatus::Rejected },
+                winning_option,
+                failing_check,
+            }
+        }
+
+        /// Move an active proposal between deciding and confirming as its support changes
+        fn refresh_decision_state(&mut self, proposal: &Proposal, current_time: Timestamp) -> DecisionState {
+            let previous = self.decision_states.get(proposal.id).unwrap_or(DecisionState::Deciding);
+            if self.confirmation_period == 0 || proposal.status != ProposalStatus::Active {
+                return previous;
+            }
+            
+            let meets = self.meets_pass_conditions(proposal, current_time);
+            let next = match (&previous, meets) {
+                (DecisionState::Deciding, true) => {
+                    self.env().emit_event(EnteredConfirming {
+                        proposal_id: proposal.id,
+                        since: current_time,
+                    });
+                    DecisionState::Confirming { since: current_time }
+                }
+                (DecisionState::Confirming { .. }, false) => {
+                    self.env().emit_event(LeftConfirming {
+                        proposal_id: proposal.id,
+                    });
+                    DecisionState::Deciding
+                }
+                _ => previous,
+            };
+            
+            self.decision_states.insert(proposal.id, &next);
+            next
+        }
+
+        /// Finalize an active proposal once its confirmation completes or voting ends without it
+        fn finalize_by_confirmation(&mut self, proposal: Proposal, current_time: Timestamp) -> Result<ProposalStatus> {
+            let state = self.refresh_decision_state(&proposal, current_time);
+            
+            let status = match state {
+                DecisionState::Confirming { since } => {
+                    // Confirmation may finish after the voting deadline, since support can no longer change
+                    if current_time < since.saturating_add(self.confirmation_period) {
+                        return Err(Error::ProposalNotReadyForExecution);
+                    }
+                    ProposalStatus::Passed
+                }
+                DecisionState::Deciding => {
+                    if current_time <= proposal.voting_end {
+                        return Err(Error::ProposalNotReadyForExecution);
+                    }
+                    ProposalStatus::Rejected
+                }
+            };
+            
+            Ok(self.conclude_voting(proposal, status, current_time))
+        }
+
+        fn ballot_commitment_of(&self, proposal_id: u32) -> BallotCommitment {
+            self.ballot_commitments.get(proposal_id).unwrap_or_default()
+        }
+
+        /// Fold a newly cast or changed ballot into the proposal's running commitment
+        fn commit_ballot(&mut self, proposal_id: u32, voter: H160, option_index: u32, weight: u128) {
+            let mut leaf_input = Vec::with_capacity(20 + 4 + 16);
+            leaf_input.extend_from_slice(voter.as_bytes());
+            leaf_input.extend_from_slice(&option_index.to_le_bytes());
+            leaf_input.extend_from_slice(&weight.to_le_bytes());
+            let mut leaf = [0u8; 32];
+            ink::env::hash_bytes::<ink::env::hash::Blake2x256>(&leaf_input, &mut leaf);
+            
+            let mut ballots = self.ballot_commitment_of(proposal_id);
+            let mut chained = [0u8; 64];
+            chained[..32].copy_from_slice(&ballots.commitment);
+            chained[32..].copy_from_slice(&leaf);
+            ink::env::hash_bytes::<ink::env::hash::Blake2x256>(&chained, &mut ballots.commitment);
+            ballots.ballots += 1;
+            
+            self.ballot_commitments.insert(proposal_id, &ballots);
+        }
+
+        /// Summarize the proposals a batch entrypoint finalized, if it finalized any
+        fn emit_batch_finalized(&self, outcomes: &[ProposalStatus]) {
+            if outcomes.is_empty() {
+                return;
+            }
+            let count_of = |status: ProposalStatus| outcomes.iter().filter(|&outcome| *outcome == status).count() as u32;
+            self.env().emit_event(BatchFinalized {
+                count: outcomes.len() as u32,
+                passed: count_of(ProposalStatus::Passed),
+                rejected: count_of(ProposalStatus::Rejected),
+            });
+        }
+
+        /// Record the outcome of an active proposal, settle reputation and announce the result
+        fn conclude_voting(&mut self, mut proposal: Proposal, status: ProposalStatus, current_time: Timestamp) -> ProposalStatus {
+            self.on_finalized(&proposal, &status, current_time);
+            
+            // Fix the winner now so later tie-break policy changes cannot alter what executes
+            if status == ProposalStatus::Passed {
+                proposal.winning_option = self.winning_option_index(&proposal).map(|index| index as u32);
+            }
+            proposal.status = status.clone();
+            self.store_proposal(&proposal);
+            
+            self.env().emit_event(ProposalExecuted {
+                proposal_id: proposal.id,
+                status: status.clone(),
+                executor: None,
+                ballot_commitment: self.ballot_commitment_of(proposal.id).commitment,
+            });
+            
+            status
+        }
+
+        /// Award reputation once a proposal is finalized: its voters earn points if it
+        /// reached quorum and its proposer earns points if it passed
+        fn accrue_reputation(&mut self, proposal: &Proposal, status: &ProposalStatus, current_time: Timestamp) {
+            let config = self.reputation_config.clone();
+            
+            let reached_quorum = proposal.total_voters >= self.required_votes(proposal, current_time)
+                && proposal.total_voters >= self.min_distinct_participants;
+            if reached_quorum && config.voter_reward > 0 {
+                for index in 0..proposal.total_voters {
+                    if let Some(voter) = self.proposal_voters.get((proposal.id, index)) {
+                        self.add_reputation(voter, config.voter_reward);
+                    }
+                }
+            }
+            
+            if *status == ProposalStatus::Passed && config.proposer_reward > 0 {
+                self.add_reputation(proposal.proposer, config.proposer_reward);
+            }
+        }
+
+        /// Count a finalized proposal and mark its voters as present, so everyone
+        /// else is lazily counted as having missed it. Skipped while slashing is disabled,
+        /// so finalization only walks the voter list when absences matter.
+        fn record_participation(&mut self, proposal: &Proposal) {
+            if self.absence_slashing.is_none() {
+                return;
+            }
+            self.finalized_proposals += 1;
+            for index in 0..proposal.total_voters {
+                if let Some(voter) = self.proposal_voters.get((proposal.id, index)) {
+                    self.participation_marks.insert(voter, &self.finalized_proposals);
+                }
+            }
+        }
+
+        /// Consecutive finalized proposals a voter has missed since last participating or registering
+        fn missed_proposals(&self, voter: H160) -> u32 {
+            let mark = self.participation_marks.get(voter).unwrap_or(0);
+            self.finalized_proposals.saturating_sub(mark)
+        }
+
+        fn add_reputation(&mut self, account: H160, points: u128) {
+            let reputation = self.reputation.get(account).unwrap_or(0).saturating_add(points);
+            self.reputation.insert(account, &reputation);
+        }
+
+        /// Deduplicate co-authors, drop the proposer and require the rest to be registered
+        fn validate_co_authors(&self, proposer: H160, co_authors: Vec<H160>) -> Result<Vec<H160>> {
+            let mut unique: Vec<H160> = Vec::new();
+            for co_author in co_authors {
+                if co_author == proposer || unique.contains(&co_author) {
+                    continue;
+                }
+                if !self.is_voter_registered(co_author) {
+                    return Err(Error::NotRegistered);
+                }
+                unique.push(co_author);
+            }
+            
+            if unique.len() > MAX_CO_AUTHORS {
+                return Err(Error::InvalidProposal);
+            }
+            
+            Ok(unique)
+        }
+
+        /// Shared eligibility checks for casting a vote on a proposal
+        fn check_can_vote(&self, proposal: &Proposal, voter: H160, current_time: Timestamp) -> Result<()> {
+            self.check_ballot_open(proposal, voter, current_time)?;
+            
+            // Proposals pinned to an eligibility root only accept ballots with a proof
+            if self.proposal_eligibility.contains(proposal.id) {
+                return Err(Error::NotAuthorized);
+            }
+            
+            // Check if user is registered as a voter
+            if !self.is_voter_registered(voter) {
+                return Err(Error::NotRegistered);
+            }
+            
+            // Proposals targeting a subset of voters only accept ballots from that subset
+            if let Some(eligible_voters) = &proposal.governance_params.eligible_voters {
+                if !eligible_voters.contains(&voter) {
+                    return Err(Error::NotAuthorized);
+                }
+            }
+            
+            // Newly registered voters must wait out the probation period
+            if self.is_in_probation(voter, current_time) {
+                return Err(Error::VoterInProbation);
+            }
+            
+            Ok(())
+        }
+
+        /// Ensure a proposal is open for voting and the voter has not voted on it yet
+        fn check_ballot_open(&self, proposal: &Proposal, voter: H160, current_time: Timestamp) -> Result<()> {
+            // Validate proposal is active
+            if proposal.status != ProposalStatus::Active {
+                return Err(Error::ProposalNotActive);
+            }
+            
+            // Validate voting period has not ended
+            if current_time > proposal.voting_end {
+                return Err(Error::VotingPeriodEnded);
+            }
+            
+            // Prevent double voting, including through a delegate's ballot that carried this voter's weight
+            if self.votes.contains((proposal.id, voter)) || self.has_delegated_ballot(proposal.id, voter) {
+                return Err(Error::AlreadyVoted);
+            }
+            
+            Ok(())
+        }
+
+        /// Ensure a choice names an existing option by its index and exact text,
+        /// so events and stored ballots never carry a label the tally does not count
+        fn check_choice(proposal: &Proposal, choice: &VoteChoice) -> Result<()> {
+            let option = proposal.voting_options.options.get(choice.option_index as usize).ok_or(Error::InvalidProposal)?;
+            if *option != choice.option_text {
+                return Err(Error::OptionMismatch);
+            }
+            
+            Ok(())
+        }
+
+        /// Check a Merkle proof that `account` is a leaf of the tree with the given root.
+        /// Leaves are `blake2_256(account)` and each node hashes its two children in sorted order.
+        fn verify_eligibility(root: &[u8; 32], account: H160, proof: &[[u8; 32]]) -> bool {
+            let mut node = [0u8; 32];
+            ink::env::hash_bytes::<ink::env::hash::Blake2x256>(account.as_bytes(), &mut node);
+            
+            for sibling in proof {
+                let (left, right) = if node <= *sibling { (node, *sibling) } else { (*sibling, node) };
+                let mut pair = [0u8; 64];
+                pair[..32].copy_from_slice(&left);
+                pair[32..].copy_from_slice(&right);
+                ink::env::hash_bytes::<ink::env::hash::Blake2x256>(&pair, &mut node);
+            }
+            
+            node == *root
+        }
+
+        /// Whether a voter registered too recently to vote
+        fn is_in_probation(&self, voter: H160, current_time: Timestamp) -> bool {
+            match self.get_registration_time(voter) {
+                Some(registered_at) => current_time < registered_at.saturating_add(self.voting_probation),
+                None => false,
+            }
+        }
+
+        /// Open voting on a scheduled proposal once its activation time is reached
+        fn activate_if_due(proposal: &mut Proposal, current_time: Timestamp) {
+            if proposal.status != ProposalStatus::Scheduled {
+                return;
+            }
+            if let Some(activation) = proposal.governance_params.scheduled_activation {
+                if current_time >= activation {
+                    proposal.status = ProposalStatus::Active;
+                }
+            }
+        }
+
+        /// Index of the option with the most votes, resolving ties with the tie-break policy,
+        /// or None on an unresolved tie or when nobody voted. The abstain option never wins.
+        fn winning_option_index(&self, proposal: &Proposal) -> Option<usize> {
+            let max_votes = proposal.decisive_counts().map(|(_, vote_count)| vote_count).max().unwrap_or(0);
+            if max_votes == 0 {
+                return None;
+            }
+            
+            let tied: Vec<usize> = proposal.decisive_counts()
+                .filter(|(_, vote_count)| *vote_count == max_votes)
+                .map(|(index, _)| index)
+                .collect();
+            if tied.len() == 1 {
+                return tied.first().copied();
+            }
+            
+            match self.tie_break_policy {
+                TieBreakPolicy::Reject => None,
+                TieBreakPolicy::LowestIndex => tied.first().copied(),
+                // Equal last-vote times fall back to the lowest index
+                TieBreakPolicy::EarliestReached => tied.iter().copied().min_by_key(|&index| {
+                    let reached_at = self.option_last_vote.get((proposal.id, index as u32)).unwrap_or(0);
+                    (reached_at, index)
+                }),
+            }
+        }
+
+        /// Treasury balance left once the winning option's transfer is paid, failing when it cannot be.
+        /// Only a winning transfer action moves treasury funds.
+        fn balance_after_execution(&self, proposal: &Proposal) -> Result<u128> {
+            let balance = self.get_treasury_balance();
+            match proposal.winning_option.and_then(|index| proposal.voting_options.actions.get(index as usize)) {
+                Some(OutcomeAction::Transfer { amount, .. }) => balance.checked_sub(*amount).ok_or(Error::InsufficientTreasuryFunds),
+                _ => Ok(balance),
+            }
+        }
+
+        /// Decode the parameter change attached to the winning option, if any. Payloads are
+        /// checked at creation, so one that no longer decodes fails execution instead of being skipped.
+        fn winning_governance_action(&self, proposal: &Proposal) -> Result<Option<ParameterChange>> {
+            let Some(payload) = proposal.winning_option
+                .and_then(|index| proposal.voting_options.payloads.get(index as usize))
+                .filter(|payload| !payload.is_empty())
+            else {
+                return Ok(None);
+            };
+            <ParameterChange as scale::DecodeAll>::decode_all(&mut payload.as_slice())
+                .map(Some)
+                .map_err(|_| Error::ExecutionFailed)
+        }
+
+        /// Check an option action's fields for its variant
+        fn is_valid_action(action: &OutcomeAction) -> bool {
+            match action {
+                OutcomeAction::NoOp => true,
+                OutcomeAction::Transfer { recipient, amount } => *recipient != H160::zero() && *amount > 0,
+                OutcomeAction::CallContract { callee, input, .. } => {
+                    *callee != H160::zero() && input.len() <= MAX_OPTION_PAYLOAD_SIZE
+                }
+            }
+        }
+
+        /// Carry out the action attached to a proposal's winning option
+        fn perform_outcome_action(&mut self, action: &OutcomeAction) -> Result<()> {
+            match action {
+                OutcomeAction::NoOp => Ok(()),
+                OutcomeAction::Transfer { recipient, amount } => self.transfer_funds(*recipient, *amount),
+                OutcomeAction::CallContract { callee, selector, input } => {
+                    ink::env::call::build_call::<Environment>()
+                        .call(*callee)
+                        .exec_input(
+                            ink::env::call::ExecutionInput::new(ink::env::call::Selector::new(*selector))
+                                .push_arg(CallInput(input)),
+                        )
+                        .returns::<()>()
+                        .try_invoke()
+                        .map_err(|_| Error::ExecutionFailed)?
+                        .map_err(|_| Error::ExecutionFailed)
+                }
+            }
+        }
+
+        /// Reward a voter from the treasury while the proposal's budget and the balance allow it
+        fn pay_vote_reward(&mut self, proposal_id: u32, voter: H160) -> Result<()> {
+            let reward = self.vote_reward;
+            if reward == 0 {
+                return Ok(());
+            }
+            
+            let distributed = self.get_rewards_distributed(proposal_id);
+            let within_budget = distributed.checked_add(reward).is_some_and(|total| total <= self.vote_reward_budget);
+            if !within_budget || self.get_treasury_balance() < reward {
+                return Ok(());
+            }
+            
+            self.transfer_funds(voter, reward)?;
+            self.rewards_distributed.insert(proposal_id, &(distributed + reward));
+            Ok(())
+        }
+
+        /// Pay native tokens out of the treasury
+        fn transfer_funds(&mut self, recipient: H160, amount: u128) -> Result<()> {
+            if self.get_treasury_balance() < amount {
+                return Err(Error::InsufficientTreasuryFunds);
+            }
+            self.env().transfer(recipient, U256::from(amount)).map_err(|_| Error::ExecutionFailed)
+        }
+
+        /// Apply a parameter change approved by a governance proposal
+        fn apply_governance_action(&mut self, action: &GovernanceAction) {
+            match action {
+                GovernanceAction::SetDefaultQuorum(threshold) => self.default_quorum_threshold = threshold.clone(),
+                GovernanceAction::SetMinDistinctParticipants(min_participants) => self.min_distinct_participants = *min_participants,
+                GovernanceAction::SetTenureWeightPerDay(weight) => self.tenure_weight_per_day = *weight,
+                GovernanceAction::SetMaxActiveProposals(max_active) => self.max_active_proposals = *max_active,
+                GovernanceAction::SetEligibilityRoot(eligibility) => self.eligibility = eligibility.clone(),
+                GovernanceAction::Halt { until } => self.governance_halt = Some(GovernanceHalt { until: *until }),
+                GovernanceAction::Resume => self.governance_halt = None,
+                GovernanceAction::SetFlaggingBarred { account, barred } => {
+                    if *barred {
+                        self.barred_flaggers.insert(*account, &true);
+                    } else {
+                        self.barred_flaggers.remove(*account);
+                    }
+                }
+                GovernanceAction::SetReputationConfig(config) => {
+                    // A zero divisor would make every reputation weight undefined
+                    if config.divisor > 0 {
+                        self.reputation_config = config.clone();
+                    }
+                }
+            }
+        }
+
+        /// Action that would restore the parameter `action` changes to its current value,
+        /// or None when the action has no single value to restore
+        fn current_setting(&self, action: &GovernanceAction) -> Option<GovernanceAction> {
+            match action {
+                GovernanceAction::SetDefaultQuorum(_) => Some(GovernanceAction::SetDefaultQuorum(self.default_quorum_threshold.clone())),
+                GovernanceAction::SetMinDistinctParticipants(_) => Some(GovernanceAction::SetMinDistinctParticipants(self.min_distinct_participants)),
+                GovernanceAction::SetTenureWeightPerDay(_) => Some(GovernanceAction::SetTenureWeightPerDay(self.tenure_weight_per_day)),
+                GovernanceAction::SetMaxActiveProposals(_) => Some(GovernanceAction::SetMaxActiveProposals(self.max_active_proposals)),
+                GovernanceAction::SetReputationConfig(_) => Some(GovernanceAction::SetReputationConfig(self.reputation_config.clone())),
+                GovernanceAction::SetEligibilityRoot(_) => Some(GovernanceAction::SetEligibilityRoot(self.eligibility.clone())),
+                // Per-account bars are not a single parameter and cannot sunset
+                GovernanceAction::Halt { .. } | GovernanceAction::Resume | GovernanceAction::SetFlaggingBarred { .. } => None,
+            }
+        }
+
+        /// Track or clear the pending revert for the parameter a change is about to overwrite
+        fn schedule_sunset(&mut self, change: &ParameterChange, current_time: Timestamp) -> Result<()> {
+            let Some(current) = self.current_setting(&change.action) else {
+                return Ok(());
+            };
+            let parameter = core::mem::discriminant(&change.action);
+            let existing = self.pending_sunsets.iter()
+                .position(|pending| core::mem::discriminant(&pending.revert) == parameter);
+            
+            match change.sunset_after {
+                Some(sunset_after) => {
+                    let revert_at = current_time.checked_add(sunset_after).ok_or(Error::ArithmeticOverflow)?;
+                    match existing {
+                        // Extending an experiment keeps the value from before it started
+                        Some(index) => self.pending_sunsets[index].revert_at = revert_at,
+                        None => self.pending_sunsets.push(PendingSunset { revert: current, revert_at }),
+                    }
+                }
+                None => {
+                    // A permanent change renews the parameter, so nothing is left to revert
+                    if let Some(index) = existing {
+                        self.pending_sunsets.remove(index);
+                    }
+                }
+            }
+            Ok(())
+        }
+
+        /// Number of flags that suspends a proposal, or None when auto-suspension is disabled
+        fn required_flags(&self) -> Option<u32> {
+            let required = match self.flag_threshold.as_ref()? {
+                FlagThreshold::Absolute(flags) => *flags,
+                FlagThreshold::Percentage(percentage) => self.total_voters.saturating_mul(*percentage) / 100,
+            };
+            Some(required.max(1))
+        }
+
+        /// Number of voters a proposal needs to reach its quorum threshold, rounded up so that
+        /// any eligible voters at all require at least one vote
+        fn required_votes(&self, proposal: &Proposal, current_time: Timestamp) -> u32 {
+            let quorum_percentage = Self::quorum_percentage(&proposal.governance_params.quorum_threshold);
+            (self.quorum_base(proposal, current_time) * quorum_percentage).div_ceil(100)
+        }
+
+        /// Percentage of eligible voters a quorum threshold stands for
+        fn quorum_percentage(threshold: &QuorumThreshold) -> u32 {
+            match threshold {
+                QuorumThreshold::Five => 5,
+                QuorumThreshold::Ten => 10,
+                QuorumThreshold::Twenty => 20,
+                QuorumThreshold::TwentyFive => 25,
+            }
+        }
+
+        /// Number of voters a proposal's quorum is measured against
+        fn quorum_base(&self, proposal: &Proposal, current_time: Timestamp) -> u32 {
+            // Proposals pinned to an eligibility root measure quorum against its declared size
+            if let Some(eligibility) = self.proposal_eligibility.get(proposal.id) {
+                return eligibility.eligible_count;
+            }
+            
+            // Proposals targeting a subset of voters measure quorum against that subset
+            if let Some(eligible_voters) = &proposal.governance_params.eligible_voters {
+                return eligible_voters.len() as u32;
+            }
+            
+            // Voters registered at creation or, when counted at finalization, those registered now,
+            // less those still in probation who could not have voted
+            let (mut eligible_voters, counted_at) = match self.quorum_timing {
+                QuorumTiming::AtCreation => (proposal.voters_snapshot, proposal.created_at),
+                QuorumTiming::AtFinalization => (self.total_voters, current_time),
+            };
+            if !self.probation_counts_toward_quorum {
+                let in_probation = self.recent_registrations.iter()
+                    .filter(|&&registered_at| registered_at <= counted_at)
+                    .filter(|&&registered_at| current_time < registered_at.saturating_add(self.voting_probation))
+                    .count() as u32;
+                eligible_voters = eligible_voters.saturating_sub(in_probation);
+            }
+            eligible_voters
+        }
+
+        /// Effective voting weight of a voter at the given time: their own weight plus the
+        /// weight delegated to them, after the power cap
+        fn voting_weight(&self, voter: H160, current_time: Timestamp) -> Result<u128> {
+            let own = self.own_voting_weight(voter, current_time)?;
+            let delegated = self.delegated_weight(voter, current_time)?;
+            Ok(self.capped_power(own.saturating_add(delegated)))
+        }
+
+        /// Apply the power cap to a ballot's combined weight
+        fn capped_power(&self, weight: u128) -> u128 {
+            // Power above the cap is dropped rather than carried over
+            if self.max_effective_power > 0 {
+                return weight.min(self.max_effective_power);
+            }
+            weight
+        }
+
+        /// Voting weight a voter holds in their own right, after any absence slashing
+        fn own_voting_weight(&self, voter: H160, current_time: Timestamp) -> Result<u128> {
+            let earned = self.earned_voting_weight(voter, current_time)?;
+            
+            let weight = match &self.absence_slashing {
+                // The one-vote floor only protects weight that exists, so an empty stake stays at zero
+                Some(slashing) if earned > 0 && self.missed_proposals(voter) >= slashing.miss_threshold => {
+                    let slashed = earned.saturating_mul(slashing.retained_percent as u128) / 100;
+                    slashed.max(1)
+                }
+                _ => earned,
+            };
+            Ok(weight)
+        }
+
+        /// Combined own weight of the accounts currently delegating to a voter
+        fn delegated_weight(&self, delegate: H160, current_time: Timestamp) -> Result<u128> {
+            let mut total: u128 = 0;
+            for index in 0..self.delegated_counts.get(delegate).unwrap_or(0) {
+                if let Some(delegator) = self.delegators.get((delegate, index)) {
+                    total = total.saturating_add(self.own_voting_weight(delegator, current_time)?);
+                }
+            }
+            Ok(total)
+        }
+
+        /// Claim the weight of a delegate's delegators who could still vote on the proposal
+        /// themselves, marking it as cast through the delegate's ballot
+        fn claim_delegated_weight(&mut self, proposal: &Proposal, delegate: H160, current_time: Timestamp) -> Result<u128> {
+            let mut claimed: u128 = 0;
+            for index in 0..self.delegated_counts.get(delegate).unwrap_or(0) {
+                let Some(delegator) = self.delegators.get((delegate, index)) else {
+                    continue;
+                };
+                if self.check_can_vote(proposal, delegator, current_time).is_err() {
+                    continue;
+                }
+                claimed = claimed.saturating_add(self.own_voting_weight(delegator, current_time)?);
+                self.delegated_ballots.insert((proposal.id, delegator), &delegate);
+            }
+            Ok(claimed)
+        }
+
+        /// Whether a voter's weight was cast on a proposal through a delegate's ballot that still stands
+        fn has_delegated_ballot(&self, proposal_id: u32, voter: H160) -> bool {
+            self.delegated_ballots.get((proposal_id, voter))
+                .is_some_and(|delegate| self.votes.contains((proposal_id, delegate)))
+        }
+
+        /// Whether an account's weight is on a ballot of a proposal that has not been finalized,
+        /// cast by the account itself or by its delegate
+        fn has_open_ballot(&self, account: H160) -> bool {
+            self.open_proposals.iter()
+                .any(|&proposal_id| self.votes.contains((proposal_id, account)) || self.has_delegated_ballot(proposal_id, account))
+        }
+
+        /// Drop an account's outgoing delegation, returning the former delegate
+        fn remove_delegation(&mut self, delegator: H160) -> Option<H160> {
+            let delegate = self.delegations.take(delegator)?;
+            let position = self.delegator_positions.take(delegator).unwrap_or(0);
+            
+            // Swap the last delegator into the freed slot to keep the index dense
+            let last_position = self.delegated_counts.get(delegate).unwrap_or(1).saturating_sub(1);
+            if position != last_position {
+                if let Some(last_delegator) = self.delegators.get((delegate, last_position)) {
+                    self.delegators.insert((delegate, position), &last_delegator);
+                    self.delegator_positions.insert(last_delegator, &position);
+                }
+            }
+            self.delegators.remove((delegate, last_position));
+            self.delegated_counts.insert(delegate, &last_position);
+            
+            Some(delegate)
+        }
+
+        /// Voting weight from stake, or from the assigned base plus tenure or reputation, before slashing
+        fn earned_voting_weight(&self, voter: H160, current_time: Timestamp) -> Result<u128> {
+            if self.weight_source == WeightSource::Stake {
+                return Ok(self.get_stake(voter));
+            }
+            
+            let base_weight = self.get_assigned_weight(voter);
+            if self.weight_source == WeightSource::Reputation {
+                let config = &self.reputation_config;
+                let bonus = self.reputation.get(voter).unwrap_or(0) / config.divisor;
+                return Ok(base_weight.saturating_add(bonus).min(config.max_weight));
+            }
+            
+            if self.tenure_weight_per_day == 0 {
+                return Ok(base_weight);
+            }
+            
+            let registered_at = self.get_registration_time(voter).unwrap_or(current_time);
+            let days_registered = current_time.saturating_sub(registered_at) / MILLIS_PER_DAY;
+            let tenure_bonus = (days_registered as u128).checked_mul(self.tenure_weight_per_day)
+                .ok_or(Error::InvalidProposal)?;
+            
+            base_weight.checked_add(tenure_bonus).ok_or(Error::InvalidProposal)
+        }
+    }
+
+    /// Internal lifecycle hook points. Optional features plug in here rather than
+    /// being threaded through the messages that drive a proposal's lifecycle
+    trait GovernanceHooks {
+        /// Runs once a new proposal is stored, before `ProposalCreated` is emitted
+        fn on_proposal_created(&mut self, proposal: &Proposal);
+        /// Runs once a ballot is recorded, before the updated tally is stored
+        fn on_vote_cast(&mut self, proposal_id: u32, voter: H160, option_index: u32, weight: u128, current_time: Timestamp) -> Result<()>;
+        /// Runs once a ballot is moved to another option, before the updated tally is stored
+        fn on_vote_changed(&mut self, proposal_id: u32, voter: H160, option_index: u32, weight: u128, current_time: Timestamp);
+        /// Runs when voting concludes, before the final status is stored and announced
+        fn on_finalized(&mut self, proposal: &Proposal, status: &ProposalStatus, current_time: Timestamp);
+        /// Runs when a passed proposal executes, before it is marked executed
+        fn on_executed(&mut self, proposal: &Proposal, current_time: Timestamp) -> Result<()>;
+    }
+
+    impl GovernanceHooks for TreasuryGovernance {
+        fn on_proposal_created(&mut self, proposal: &Proposal) {
+            #[cfg(test)]
+            hook_trace::record("on_proposal_created");
+            
+            // Indices: open proposals and the eligibility root active at creation
+            self.open_proposals.push(proposal.id);
+            if let Some(eligibility) = &self.eligibility {
+                self.proposal_eligibility.insert(proposal.id, eligibility);
+            }
+            
+            // Stats: the most recent proposals for the dashboard and the running count
+            if self.recent_proposals.len() >= RECENT_PROPOSALS_SIZE {
+                self.recent_proposals.remove(0);
+            }
+            self.recent_proposals.push(proposal.id);
+            self.proposal_count += 1;
+            
+            // Proposer activity
+            if let Some(mut info) = self.voter_info_of(proposal.proposer) {
+                info.proposals_created = info.proposals_created.saturating_add(1);
+                info.last_active_at = proposal.created_at;
+                self.store_voter_info(proposal.proposer, &info);
+            }
+        }
+
+        fn on_vote_cast(&mut self, proposal_id: u32, voter: H160, option_index: u32, weight: u128, current_time: Timestamp) -> Result<()> {
+            #[cfg(test)]
+            hook_trace::record("on_vote_cast");
+            
+            // Tie-break tracking and the ballot commitment
+            self.option_last_vote.insert((proposal_id, option_index), &current_time);
+            self.commit_ballot(proposal_id, voter, option_index, weight);
+            
+            // Voter activity; voters admitted by an eligibility proof have no record
+            if let Some(mut info) = self.voter_info_of(voter) {
+                info.votes_cast = info.votes_cast.saturating_add(1);
+                info.last_active_at = current_time;
+                self.store_voter_info(voter, &info);
+            }
+            
+            // Rewards
+            self.pay_vote_reward(proposal_id, voter)
+        }
+
+        fn on_vote_changed(&mut self, proposal_id: u32, voter: H160, option_index: u32, weight: u128, current_time: Timestamp) {
+            #[cfg(test)]
+            hook_trace::record("on_vote_changed");
+            
+            // Tie-break tracking and the ballot commitment, which records the change as a new ballot
+            self.option_last_vote.insert((proposal_id, option_index), &current_time);
+            self.commit_ballot(proposal_id, voter, option_index, weight);
+            
+            // An audit in progress may already have counted the ballot under its old option
+            self.tally_audits.remove(proposal_id);
+            
+            // Voter activity; a change is not a new vote
+            if let Some(mut info) = self.voter_info_of(voter) {
+                info.last_active_at = current_time;
+                self.store_voter_info(voter, &info);
+            }
+        }
+
+        fn on_finalized(&mut self, proposal: &Proposal, status: &ProposalStatus, current_time: Timestamp) {
+            #[cfg(test)]
+            hook_trace::record("on_finalized");
+            
+            // Reputation and absence tracking
+            self.accrue_reputation(proposal, status, current_time);
+            self.record_participation(proposal);
+            
+            // Seal the ballot commitment so auditors can check it against the counted votes
+            let mut ballots = self.ballot_commitment_of(proposal.id);
+            ballots.sealed = true;
+            self.ballot_commitments.insert(proposal.id, &ballots);
+        }
+
+        fn on_executed(&mut self, proposal: &Proposal, current_time: Timestamp) -> Result<()> {
+            #[cfg(test)]
+            hook_trace::record("on_executed");
+            
+            // Dispatch the action attached to the option that won at finalization, if any
+            if let Some(action) = proposal.winning_option
+                .and_then(|index| proposal.voting_options.actions.get(index as usize))
+            {
+                self.perform_outcome_action(action)?;
+            }
+            
+            // Apply the parameter change carried by the winning option of a governance proposal
+            if proposal.proposal_type == ProposalType::Governance {
+                if let Some(change) = self.winning_governance_action(proposal)? {
+                    self.schedule_sunset(&change, current_time)?;
+                    self.apply_governance_action(&change.action);
+                    self.env().emit_event(GovernanceActionApplied {
+                        proposal_id: proposal.id,
+                        action: change.action,
+                    });
+                }
+            }
+            Ok(())
+        }
     }
 
 }
@@ -3,6 +3,57 @@ use ink::prelude::string::String;
 use ink::prelude::vec::Vec;
 use ink::primitives::H160;
 
+/// Block time in milliseconds, as returned by `block_timestamp`; durations use the same unit
+pub type Timestamp = u64;
+
+/// Maximum number of entries returned by a single paginated query
+pub const MAX_PAGE_SIZE: u32 = 50;
+
+/// Number of milliseconds in a day; block timestamps and all durations are in milliseconds
+pub const MILLIS_PER_DAY: Timestamp = 24 * 60 * 60 * 1000;
+
+/// Number of milliseconds in a second, for getters that report durations in seconds
+pub const MILLIS_PER_SECOND: Timestamp = 1000;
+
+/// Maximum length of a proposal title in bytes
+pub const MAX_TITLE_LENGTH: usize = 256;
+
+/// Maximum length of a proposal description in bytes
+pub const MAX_DESCRIPTION_LENGTH: usize = 4096;
+
+/// Maximum size of a single option payload in bytes
+pub const MAX_OPTION_PAYLOAD_SIZE: usize = 256;
+
+/// Maximum custom execution delay a proposal may request, in milliseconds
+pub const MAX_EXECUTION_DELAY: Timestamp = 30 * MILLIS_PER_DAY;
+
+/// Grace period of new proposals until the owner sets another default, in milliseconds
+pub const DEFAULT_GRACE_PERIOD: Timestamp = 30 * MILLIS_PER_DAY;
+
+/// Default minimum time a proposal stays open for voting after creation
+pub const DEFAULT_MIN_VOTING_WINDOW: Timestamp = 60 * 60 * 1000;
+
+/// Window before the end of voting in which a proposal is reported as expiring soon
+pub const EXPIRING_SOON_WINDOW: Timestamp = MILLIS_PER_DAY;
+
+/// Maximum number of designated voters a proposal may restrict voting to
+pub const MAX_ELIGIBLE_VOTERS: usize = 100;
+
+/// Maximum number of co-authors on a proposal besides the proposer
+pub const MAX_CO_AUTHORS: usize = 5;
+
+/// Number of recently created proposals shown on the dashboard
+pub const RECENT_PROPOSALS_SIZE: usize = 5;
+
+/// Layout version of the `Dashboard` struct, bumped whenever fields change
+pub const DASHBOARD_VERSION: u32 = 3;
+
+/// Maximum number of proposals a single account can watch
+pub const MAX_WATCHLIST_SIZE: usize = 100;
+
+/// Maximum number of accounts that may delegate to a single delegate
+pub const MAX_DELEGATORS: u32 = 50;
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[ink::scale_derive(Encode, Decode, TypeInfo)]
 #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
@@ -50,6 +101,27 @@ pub struct GovernanceParameters {
     pub voting_period: VotingPeriod,
     pub quorum_threshold: QuorumThreshold,
     pub execution_delay: ExecutionDelay,
+    /// Custom execution delay in milliseconds, superseding `execution_delay` when set
+    pub execution_delay_override: Option<Timestamp>,
+    /// Future time at which voting opens; the voting period starts from it
+    pub scheduled_activation: Option<Timestamp>,
+    /// Subset of registered voters allowed to vote on the proposal, if restricted
+    pub eligible_voters: Option<Vec<H160>>,
+    /// External condition that must hold at execution time, if any
+    pub oracle: Option<OracleCondition>,
+    /// How long after `execution_time` a passed proposal stays executable; None takes the contract default
+    pub grace_period: Option<Timestamp>,
+    /// Whether the proposal can still be cancelled once votes have been cast
+    pub allow_cancel_after_votes: bool,
+}
+
+/// Oracle contract queried before execution; the message must take no arguments and return `bool`
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[ink::scale_derive(Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+pub struct OracleCondition {
+    pub oracle: H160,
+    pub selector: [u8; 4],
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -57,6 +129,25 @@ pub struct GovernanceParameters {
 #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
 pub struct VotingOptions {
     pub options: Vec<String>,
+    /// Optional SCALE-encoded payload per option, either empty or one entry per option
+    pub payloads: Vec<Vec<u8>>,
+    /// Optional action executed when the option wins, either empty or one entry per option
+    pub actions: Vec<OutcomeAction>,
+    /// Option that counts toward quorum but can never win, if any
+    pub abstain_index: Option<u32>,
+}
+
+/// Effect of executing a proposal, chosen by its winning option
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[ink::scale_derive(Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+pub enum OutcomeAction {
+    /// Only mark the proposal as executed
+    NoOp,
+    /// Pay `amount` from the treasury to `recipient`
+    Transfer { recipient: H160, amount: u128 },
+    /// Call `selector` on `callee` with SCALE-encoded `input` arguments
+    CallContract { callee: H160, selector: [u8; 4], input: Vec<u8> },
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -71,11 +162,21 @@ pub struct VoteChoice {
 #[ink::scale_derive(Encode, Decode, TypeInfo)]
 #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
 pub enum ProposalStatus {
+    Scheduled,
     Active,
+    Suspended,
     Passed,
     Rejected,
     Executed,
     Expired,
+    Cancelled,
+}
+
+impl ProposalStatus {
+    /// Whether the proposal has reached a final state and can no longer change
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, ProposalStatus::Rejected | ProposalStatus::Executed | ProposalStatus::Expired | ProposalStatus::Cancelled)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -89,12 +190,396 @@ pub struct Proposal {
     pub governance_params: GovernanceParameters,
     pub voting_options: VotingOptions,
     pub proposer: H160,
-    pub created_at: u32,
-    pub voting_end: u32,
-    pub execution_time: u32,
+    pub created_at: Timestamp,
+    pub voting_end: Timestamp,
+    pub execution_time: Timestamp,
     pub status: ProposalStatus,
     pub vote_counts: Vec<u128>,
     pub total_voters: u32,
+    pub endorsement_count: u32,
+    /// Time the proposal was suspended, while it is on hold
+    pub suspended_at: Option<Timestamp>,
+    /// Registered voters credited alongside the proposer
+    pub co_authors: Vec<H160>,
+    /// Account that executed the proposal
+    pub executed_by: Option<H160>,
+    /// Time the proposal was executed
+    pub executed_at: Option<Timestamp>,
+    /// Proposal this one supersedes as a revision
+    pub revision_of: Option<u32>,
+    /// Registered voters when the proposal was created, the quorum denominator for its lifetime
+    pub voters_snapshot: u32,
+    /// Option that won when voting concluded, the one execution acts on
+    pub winning_option: Option<u32>,
+}
+
+impl Proposal {
+    /// Whether an account is the proposer or one of the co-authors
+    pub fn is_author(&self, account: H160) -> bool {
+        self.proposer == account || self.co_authors.contains(&account)
+    }
+
+    /// Vote counts by option index, leaving out the abstain option
+    pub fn decisive_counts(&self) -> impl Iterator<Item = (usize, u128)> + '_ {
+        self.vote_counts.iter()
+            .copied()
+            .enumerate()
+            .filter(|(index, _)| self.voting_options.abstain_index != Some(*index as u32))
+    }
+}
+
+/// Decision sub-state of an active proposal when a confirmation period is configured
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[ink::scale_derive(Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+pub enum DecisionState {
+    /// Voting is open but the proposal does not currently meet its thresholds
+    Deciding,
+    /// The proposal has met its thresholds continuously since the given time
+    Confirming { since: Timestamp },
+}
+
+/// Running hash commitment over a proposal's ballots, sealed at finalization
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[ink::scale_derive(Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+pub struct BallotCommitment {
+    pub commitment: [u8; 32],
+    pub ballots: u32,
+    pub sealed: bool,
+}
+
+/// Parameter change applied when a governance proposal's winning option carries it
+/// as its SCALE-encoded payload
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[ink::scale_derive(Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+pub enum GovernanceAction {
+    SetDefaultQuorum(QuorumThreshold),
+    SetMinDistinctParticipants(u32),
+    SetTenureWeightPerDay(u128),
+    SetMaxActiveProposals(u32),
+    SetReputationConfig(ReputationConfig),
+    SetEligibilityRoot(Option<EligibilityRoot>),
+    /// Block new proposals until the given time, or until a `Resume` executes
+    Halt { until: Option<Timestamp> },
+    Resume,
+    /// Bar an account from flagging proposals, or lift the bar
+    SetFlaggingBarred { account: H160, barred: bool },
+}
+
+/// Number of flags that automatically suspends a proposal
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[ink::scale_derive(Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+pub enum FlagThreshold {
+    /// Fixed number of flags
+    Absolute(u32),
+    /// Percentage of registered voters
+    Percentage(u32),
+}
+
+/// Halt on proposal creation voted in by governance
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[ink::scale_derive(Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+pub struct GovernanceHalt {
+    /// Time the halt lapses on its own, if any
+    pub until: Option<Timestamp>,
+}
+
+/// Payload of a governance option: the action to apply and, optionally, how long it lasts
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[ink::scale_derive(Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+pub struct ParameterChange {
+    pub action: GovernanceAction,
+    /// Milliseconds after execution at which the previous value is restored (None keeps it permanently)
+    pub sunset_after: Option<Timestamp>,
+}
+
+/// Previous parameter value waiting to be restored once its sunset passes
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[ink::scale_derive(Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+pub struct PendingSunset {
+    /// Action that restores the value in place before the experimental change
+    pub revert: GovernanceAction,
+    pub revert_at: Timestamp,
+}
+
+/// Weight reduction applied to voters who miss consecutive finalized proposals
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[ink::scale_derive(Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+pub struct AbsenceSlashing {
+    /// Consecutive misses after which slashing applies
+    pub miss_threshold: u32,
+    /// Percentage of voting weight kept while slashed (never below one vote unless the weight is zero)
+    pub retained_percent: u32,
+}
+
+/// Merkle root of eligible voter addresses, built off-chain
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[ink::scale_derive(Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+pub struct EligibilityRoot {
+    pub root: [u8; 32],
+    /// Number of eligible addresses, used as the quorum denominator
+    pub eligible_count: u32,
+}
+
+/// Source of a voter's voting weight
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[ink::scale_derive(Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+pub enum WeightSource {
+    /// One vote plus a bonus per day of membership
+    Tenure,
+    /// One vote plus reputation earned from governance history
+    Reputation,
+    /// The native tokens a voter has staked
+    Stake,
+}
+
+/// How a tie between the leading options is resolved
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[ink::scale_derive(Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+pub enum TieBreakPolicy {
+    /// No winner, so the proposal is rejected
+    Reject,
+    /// The tied option listed first wins
+    LowestIndex,
+    /// The tied option whose last vote came earliest reached its total first and wins
+    EarliestReached,
+}
+
+/// When the electorate that quorum is measured against is counted
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[ink::scale_derive(Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+pub enum QuorumTiming {
+    /// Voters registered when the proposal was created
+    AtCreation,
+    /// Voters still registered when the proposal is finalized
+    AtFinalization,
+}
+
+/// Reputation accrual rates and how reputation converts into voting weight
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[ink::scale_derive(Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+pub struct ReputationConfig {
+    /// Points awarded to the proposer when their proposal passes
+    pub proposer_reward: u128,
+    /// Points awarded to each voter on a proposal that reached quorum
+    pub voter_reward: u128,
+    /// Reputation points per extra unit of voting weight (must be non-zero)
+    pub divisor: u128,
+    /// Upper bound on reputation-based voting weight
+    pub max_weight: u128,
+}
+
+impl Default for ReputationConfig {
+    fn default() -> Self {
+        Self {
+            proposer_reward: 10,
+            voter_reward: 1,
+            divisor: 10,
+            max_weight: 10,
+        }
+    }
+}
+
+/// Allowed voting period and execution delay range for a proposal type, in milliseconds
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[ink::scale_derive(Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+pub struct DurationBounds {
+    pub min_voting: Timestamp,
+    pub max_voting: Timestamp,
+    pub min_delay: Timestamp,
+}
+
+impl Default for DurationBounds {
+    fn default() -> Self {
+        Self {
+            min_voting: 0,
+            max_voting: Timestamp::MAX,
+            min_delay: 0,
+        }
+    }
+}
+
+/// Minimum title and description lengths in bytes for a proposal type
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[ink::scale_derive(Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+pub struct LengthMinimums {
+    pub min_title: u32,
+    pub min_description: u32,
+}
+
+/// Progress of a chunked tally audit
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[ink::scale_derive(Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+pub struct TallyAudit {
+    /// Counts rebuilt from the vote records audited so far
+    pub recomputed: Vec<u128>,
+    /// Voter index the next chunk must start from
+    pub next_index: u32,
+}
+
+/// Result of auditing one chunk of a proposal's vote records
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[ink::scale_derive(Encode, Decode, TypeInfo)]
+pub struct AuditReport {
+    pub next_offset: u32,
+    pub complete: bool,
+    /// Whether the recount matches the stored counts, once the audit is complete
+    pub consistent: Option<bool>,
+    pub stored: Vec<u128>,
+    pub recomputed: Vec<u128>,
+}
+
+/// Check that keeps a proposal from passing at finalization
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[ink::scale_derive(Encode, Decode, TypeInfo)]
+pub enum FinalizationCheck {
+    Quorum,
+    Participation,
+    NoVotes,
+    Tie,
+    Confirmation,
+}
+
+/// Outcome finalization would produce for a proposal's current tallies
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[ink::scale_derive(Encode, Decode, TypeInfo)]
+pub struct SimulatedOutcome {
+    /// Would-be status, or `Active` while a confirmation period is still running
+    pub status: ProposalStatus,
+    pub winning_option: Option<u32>,
+    /// First failing check, if the proposal would not pass
+    pub failing_check: Option<FinalizationCheck>,
+}
+
+/// Work item reported to keepers by `get_pending_actions`
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[ink::scale_derive(Encode, Decode, TypeInfo)]
+pub enum PendingAction {
+    NeedsFinalization(u32),
+    ReadyToExecute(u32),
+    ExpiringSoon { proposal_id: u32, deadline: Timestamp },
+}
+
+/// Number of proposals in each status, maintained on every status change
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[ink::scale_derive(Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+pub struct StatusCounts {
+    pub scheduled: u32,
+    pub active: u32,
+    pub suspended: u32,
+    pub passed: u32,
+    pub rejected: u32,
+    pub executed: u32,
+    pub expired: u32,
+    pub cancelled: u32,
+}
+
+impl StatusCounts {
+    fn counter_mut(&mut self, status: &ProposalStatus) -> &mut u32 {
+        match status {
+            ProposalStatus::Scheduled => &mut self.scheduled,
+            ProposalStatus::Active => &mut self.active,
+            ProposalStatus::Suspended => &mut self.suspended,
+            ProposalStatus::Passed => &mut self.passed,
+            ProposalStatus::Rejected => &mut self.rejected,
+            ProposalStatus::Executed => &mut self.executed,
+            ProposalStatus::Expired => &mut self.expired,
+            ProposalStatus::Cancelled => &mut self.cancelled,
+        }
+    }
+
+    pub fn increment(&mut self, status: &ProposalStatus) {
+        let counter = self.counter_mut(status);
+        *counter = counter.saturating_add(1);
+    }
+
+    pub fn decrement(&mut self, status: &ProposalStatus) {
+        let counter = self.counter_mut(status);
+        *counter = counter.saturating_sub(1);
+    }
+}
+
+/// Overview of the DAO returned by `get_dashboard`
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[ink::scale_derive(Encode, Decode, TypeInfo)]
+pub struct Dashboard {
+    pub version: u32,
+    pub owner: H160,
+    pub guardian: H160,
+    pub total_voters: u32,
+    pub proposal_count: u32,
+    pub status_counts: StatusCounts,
+    pub treasury_balance: u128,
+    pub tenure_weight_per_day: u128,
+    pub min_distinct_participants: u32,
+    pub voting_probation: Timestamp,
+    pub open_proposal_ids: Vec<u32>,
+    pub recent_proposal_ids: Vec<u32>,
+}
+
+/// Owner-configurable numeric limits returned by `get_limits`
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[ink::scale_derive(Encode, Decode, TypeInfo)]
+pub struct Limits {
+    pub max_active_proposals: u32,
+    pub max_voters: u32,
+    pub min_distinct_participants: u32,
+    pub tenure_weight_per_day: u128,
+    pub voting_probation: Timestamp,
+    pub max_reasonable_timestamp: Timestamp,
+    pub confirmation_period: Timestamp,
+    pub min_voting_window: Timestamp,
+    pub min_weight_to_propose: u128,
+    pub max_effective_power: u128,
+    pub vote_reward: u128,
+    pub vote_reward_budget: u128,
+    pub max_reputation_weight: u128,
+}
+
+/// Lightweight view of a proposal for list displays
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[ink::scale_derive(Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+pub struct ProposalSummary {
+    pub id: u32,
+    pub title: String,
+    pub proposal_type: ProposalType,
+    pub status: ProposalStatus,
+    pub proposer: H160,
+    pub voting_end: Timestamp,
+    pub total_voters: u32,
+    pub endorsement_count: u32,
+}
+
+impl From<&Proposal> for ProposalSummary {
+    fn from(proposal: &Proposal) -> Self {
+        Self {
+            id: proposal.id,
+            title: proposal.title.clone(),
+            proposal_type: proposal.proposal_type.clone(),
+            status: proposal.status.clone(),
+            proposer: proposal.proposer,
+            voting_end: proposal.voting_end,
+            total_voters: proposal.total_voters,
+            endorsement_count: proposal.endorsement_count,
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -103,6 +588,40 @@ pub struct Proposal {
 pub struct Vote {
     pub voter: H160,
     pub choice: VoteChoice,
-    pub timestamp: u32,
+    pub timestamp: Timestamp,
+    pub weight: u128,
+}
+
+/// Membership tier of a registered voter
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[ink::scale_derive(Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+pub enum VoterTier {
+    Member,
+    Council,
+}
+
+/// Registration metadata and activity of a registered voter
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[ink::scale_derive(Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+pub struct VoterInfo {
+    pub registered_at: Timestamp,
+    /// Account that performed the registration: the voter or the admin
+    pub registered_by: H160,
+    pub tier: VoterTier,
+    pub votes_cast: u32,
+    pub proposals_created: u32,
+    pub last_active_at: Timestamp,
+}
+
+/// Ballot as kept in storage; the voter is part of the mapping key and the
+/// option text is looked up on the proposal when rebuilding a `Vote`
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[ink::scale_derive(Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+pub struct StoredVote {
+    pub option_index: u16,
+    pub timestamp: Timestamp,
     pub weight: u128,
 }
\ No newline at end of file